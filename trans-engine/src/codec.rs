@@ -0,0 +1,610 @@
+//! Compact, self-describing binary encoding for compiled rule programs.
+//!
+//! After [`crate::parser::parse_line`] and [`Rule::simplify`], the resulting
+//! `Vec<Rule>` can't be cached or shipped between processes as-is. [`compile`]
+//! parses and simplifies a list of rule lines once and encodes them into a
+//! byte program; [`run_compiled`] decodes that program and runs it over a
+//! wordlist without re-parsing. This is especially useful for the pyo3 layer,
+//! which would otherwise re-parse the same rule file on every call.
+//!
+//! Each rule is tagged with a single opcode byte (see the `tag` module),
+//! followed by its operands as LEB128 varints and length-prefixed strings -
+//! a scheme in the spirit of cozo's `Tag` encoding.
+
+use crate::engine;
+use crate::lang::{Case, CharClass, RejectRule, Rotation, Rule, RuleMode, TransformRule, Truncate};
+use crate::parser::parse_line;
+
+mod tag {
+    pub const NO_OP: u8 = 0x00;
+    pub const END: u8 = 0x01;
+
+    pub const LOWERCASE: u8 = 0x10;
+    pub const UPPERCASE: u8 = 0x11;
+    pub const CAPITALIZE: u8 = 0x12;
+    pub const INVERT_CAPITALIZE: u8 = 0x13;
+    pub const TOGGLE_CASE: u8 = 0x14;
+    pub const REVERSE: u8 = 0x15;
+    pub const DUPLICATE: u8 = 0x16;
+    pub const REFLECT: u8 = 0x17;
+    pub const ROTATE: u8 = 0x18;
+    pub const APPEND: u8 = 0x19;
+    pub const PREPEND: u8 = 0x1a;
+    pub const DELETE: u8 = 0x1b;
+    pub const EXTRACT: u8 = 0x1c;
+    pub const OMIT: u8 = 0x1d;
+    pub const INSERT: u8 = 0x1e;
+    pub const OVERWRITE: u8 = 0x1f;
+    pub const TRUNCATE: u8 = 0x20;
+    pub const REPLACE: u8 = 0x21;
+    pub const PURGE: u8 = 0x22;
+    pub const DUPLICATE_FIRST: u8 = 0x23;
+    pub const DUPLICATE_LAST: u8 = 0x24;
+    pub const DUPLICATE_ALL: u8 = 0x25;
+    pub const SWAP_FRONT: u8 = 0x26;
+    pub const SWAP_BACK: u8 = 0x27;
+    pub const SWAP: u8 = 0x28;
+    pub const SHIFT_LEFT: u8 = 0x29;
+    pub const SHIFT_RIGHT: u8 = 0x2a;
+    pub const ASCII_INCREMENT: u8 = 0x2b;
+    pub const ASCII_DECREMENT: u8 = 0x2c;
+    pub const REPLACE_WITH_NEXT: u8 = 0x2d;
+    pub const REPLACE_WITH_PREV: u8 = 0x2e;
+    pub const DUPLICATE_FIRST_BLOCK: u8 = 0x2f;
+    pub const DUPLICATE_LAST_BLOCK: u8 = 0x30;
+    pub const REGEX_REPLACE: u8 = 0x31;
+    pub const TO_CASE: u8 = 0x32;
+    pub const TITLE_CASE: u8 = 0x33;
+    pub const ALTERNATING_WORD_CASE: u8 = 0x34;
+    pub const CASE_PERMUTE: u8 = 0x35;
+    pub const LEET_REPLACE: u8 = 0x36;
+
+    pub const SHORTER_THAN: u8 = 0x50;
+    pub const LONGER_THAN: u8 = 0x51;
+    pub const NOT_EQUAL_TO: u8 = 0x52;
+    pub const CONTAINS: u8 = 0x53;
+    pub const NOT_CONTAINS: u8 = 0x54;
+    pub const NOT_STARTS_WITH: u8 = 0x55;
+    pub const NOT_ENDS_WITH: u8 = 0x56;
+    pub const NOT_EQUAL_AT: u8 = 0x57;
+    pub const CONTAINS_LESS_THAN: u8 = 0x58;
+    pub const REGEX_MATCH: u8 = 0x59;
+    pub const REGEX_NOT_MATCH: u8 = 0x5a;
+    pub const REQUIRES_CLASS: u8 = 0x5b;
+    pub const ALLOWED_ONLY: u8 = 0x5c;
+
+    pub const ROTATE_LEFT: u8 = 0;
+    pub const ROTATE_RIGHT: u8 = 1;
+
+    pub const TRUNCATE_LEFT: u8 = 0;
+    pub const TRUNCATE_RIGHT: u8 = 1;
+    pub const TRUNCATE_TO: u8 = 2;
+
+    pub const CASE_TITLE: u8 = 0;
+    pub const CASE_CAMEL: u8 = 1;
+    pub const CASE_PASCAL: u8 = 2;
+    pub const CASE_SNAKE: u8 = 3;
+    pub const CASE_KEBAB: u8 = 4;
+    pub const CASE_SCREAMING_SNAKE: u8 = 5;
+
+    pub const CLASS_LOWER: u8 = 0;
+    pub const CLASS_UPPER: u8 = 1;
+    pub const CLASS_DIGIT: u8 = 2;
+    pub const CLASS_SPECIAL: u8 = 3;
+    pub const CLASS_UNICODE: u8 = 4;
+}
+
+fn write_varint(buf: &mut Vec<u8>, mut n: u64) {
+    loop {
+        let byte = (n & 0x7f) as u8;
+        n >>= 7;
+        if n == 0 {
+            buf.push(byte);
+            return;
+        }
+        buf.push(byte | 0x80);
+    }
+}
+
+fn read_varint(buf: &[u8], pos: &mut usize) -> Result<u64, String> {
+    let mut result = 0u64;
+    let mut shift = 0;
+    loop {
+        let byte = *buf.get(*pos).ok_or("unexpected end of program while reading a number")?;
+        *pos += 1;
+        result |= ((byte & 0x7f) as u64) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(result);
+        }
+        shift += 7;
+    }
+}
+
+fn write_usize(buf: &mut Vec<u8>, n: usize) {
+    write_varint(buf, n as u64);
+}
+
+fn read_usize(buf: &[u8], pos: &mut usize) -> Result<usize, String> {
+    read_varint(buf, pos).map(|n| n as usize)
+}
+
+fn write_option_usize(buf: &mut Vec<u8>, n: Option<usize>) {
+    match n {
+        Some(n) => {
+            buf.push(1);
+            write_usize(buf, n);
+        }
+        None => buf.push(0),
+    }
+}
+
+fn read_option_usize(buf: &[u8], pos: &mut usize) -> Result<Option<usize>, String> {
+    let present = *buf.get(*pos).ok_or("unexpected end of program while reading an optional number")?;
+    *pos += 1;
+    if present == 0 {
+        Ok(None)
+    } else {
+        read_usize(buf, pos).map(Some)
+    }
+}
+
+fn write_string(buf: &mut Vec<u8>, s: &str) {
+    write_usize(buf, s.len());
+    buf.extend_from_slice(s.as_bytes());
+}
+
+fn read_string(buf: &[u8], pos: &mut usize) -> Result<String, String> {
+    let len = read_usize(buf, pos)?;
+    let end = pos
+        .checked_add(len)
+        .filter(|&end| end <= buf.len())
+        .ok_or("string length out of bounds")?;
+    let s = std::str::from_utf8(&buf[*pos..end]).map_err(|e| e.to_string())?.to_string();
+    *pos = end;
+    Ok(s)
+}
+
+fn write_char(buf: &mut Vec<u8>, c: char) {
+    write_usize(buf, c as usize);
+}
+
+fn read_char(buf: &[u8], pos: &mut usize) -> Result<char, String> {
+    let codepoint = read_usize(buf, pos)?;
+    char::from_u32(codepoint as u32).ok_or_else(|| format!("invalid character codepoint {codepoint:#x}"))
+}
+
+fn write_chars(buf: &mut Vec<u8>, chars: &[char]) {
+    write_usize(buf, chars.len());
+    for &c in chars {
+        write_char(buf, c);
+    }
+}
+
+fn read_chars(buf: &[u8], pos: &mut usize) -> Result<Vec<char>, String> {
+    let len = read_usize(buf, pos)?;
+    (0..len).map(|_| read_char(buf, pos)).collect()
+}
+
+fn write_char_class(buf: &mut Vec<u8>, class: &CharClass) {
+    buf.push(match class {
+        CharClass::Lower => tag::CLASS_LOWER,
+        CharClass::Upper => tag::CLASS_UPPER,
+        CharClass::Digit => tag::CLASS_DIGIT,
+        CharClass::Special => tag::CLASS_SPECIAL,
+        CharClass::Unicode => tag::CLASS_UNICODE,
+    });
+}
+
+fn read_char_class(buf: &[u8], pos: &mut usize) -> Result<CharClass, String> {
+    let byte = *buf.get(*pos).ok_or("unexpected end of program while reading a character class")?;
+    *pos += 1;
+    match byte {
+        tag::CLASS_LOWER => Ok(CharClass::Lower),
+        tag::CLASS_UPPER => Ok(CharClass::Upper),
+        tag::CLASS_DIGIT => Ok(CharClass::Digit),
+        tag::CLASS_SPECIAL => Ok(CharClass::Special),
+        tag::CLASS_UNICODE => Ok(CharClass::Unicode),
+        other => Err(format!("unknown character class tag {other:#04x}")),
+    }
+}
+
+fn write_transform(buf: &mut Vec<u8>, rule: &TransformRule) {
+    match rule {
+        TransformRule::Lowercase => buf.push(tag::LOWERCASE),
+        TransformRule::Uppercase => buf.push(tag::UPPERCASE),
+        TransformRule::Capitalize => buf.push(tag::CAPITALIZE),
+        TransformRule::InvertCapitalize => buf.push(tag::INVERT_CAPITALIZE),
+        TransformRule::ToggleCase(n) => {
+            buf.push(tag::TOGGLE_CASE);
+            write_option_usize(buf, *n);
+        }
+        TransformRule::Reverse => buf.push(tag::REVERSE),
+        TransformRule::Duplicate(n) => {
+            buf.push(tag::DUPLICATE);
+            write_option_usize(buf, *n);
+        }
+        TransformRule::Reflect => buf.push(tag::REFLECT),
+        TransformRule::Rotate(rotation) => {
+            buf.push(tag::ROTATE);
+            buf.push(match rotation {
+                Rotation::Left => tag::ROTATE_LEFT,
+                Rotation::Right => tag::ROTATE_RIGHT,
+            });
+        }
+        TransformRule::Append(s) => {
+            buf.push(tag::APPEND);
+            write_string(buf, s);
+        }
+        TransformRule::Prepend(s) => {
+            buf.push(tag::PREPEND);
+            write_string(buf, s);
+        }
+        TransformRule::Delete(n) => {
+            buf.push(tag::DELETE);
+            write_usize(buf, *n);
+        }
+        TransformRule::Extract(a, b) => {
+            buf.push(tag::EXTRACT);
+            write_usize(buf, *a);
+            write_usize(buf, *b);
+        }
+        TransformRule::Omit(a, b) => {
+            buf.push(tag::OMIT);
+            write_usize(buf, *a);
+            write_usize(buf, *b);
+        }
+        TransformRule::Insert(n, s) => {
+            buf.push(tag::INSERT);
+            write_usize(buf, *n);
+            write_string(buf, s);
+        }
+        TransformRule::Overwrite(n, s) => {
+            buf.push(tag::OVERWRITE);
+            write_usize(buf, *n);
+            write_string(buf, s);
+        }
+        TransformRule::Truncate(truncate) => {
+            buf.push(tag::TRUNCATE);
+            match truncate {
+                Truncate::Left => buf.push(tag::TRUNCATE_LEFT),
+                Truncate::Right => buf.push(tag::TRUNCATE_RIGHT),
+                Truncate::To(n) => {
+                    buf.push(tag::TRUNCATE_TO);
+                    write_usize(buf, *n);
+                }
+            }
+        }
+        TransformRule::Replace(a, b) => {
+            buf.push(tag::REPLACE);
+            write_string(buf, a);
+            write_string(buf, b);
+        }
+        TransformRule::Purge(s) => {
+            buf.push(tag::PURGE);
+            write_string(buf, s);
+        }
+        TransformRule::DuplicateFirst(n) => {
+            buf.push(tag::DUPLICATE_FIRST);
+            write_usize(buf, *n);
+        }
+        TransformRule::DuplicateLast(n) => {
+            buf.push(tag::DUPLICATE_LAST);
+            write_usize(buf, *n);
+        }
+        TransformRule::DuplicateAll => buf.push(tag::DUPLICATE_ALL),
+        TransformRule::SwapFront => buf.push(tag::SWAP_FRONT),
+        TransformRule::SwapBack => buf.push(tag::SWAP_BACK),
+        TransformRule::Swap(a, b) => {
+            buf.push(tag::SWAP);
+            write_usize(buf, *a);
+            write_usize(buf, *b);
+        }
+        TransformRule::BitwiseShiftLeft(n) => {
+            buf.push(tag::SHIFT_LEFT);
+            write_usize(buf, *n);
+        }
+        TransformRule::BitwiseShiftRight(n) => {
+            buf.push(tag::SHIFT_RIGHT);
+            write_usize(buf, *n);
+        }
+        TransformRule::AsciiIncrement(n) => {
+            buf.push(tag::ASCII_INCREMENT);
+            write_usize(buf, *n);
+        }
+        TransformRule::AsciiDecrement(n) => {
+            buf.push(tag::ASCII_DECREMENT);
+            write_usize(buf, *n);
+        }
+        TransformRule::ReplaceWithNext(n) => {
+            buf.push(tag::REPLACE_WITH_NEXT);
+            write_usize(buf, *n);
+        }
+        TransformRule::ReplaceWithPrev(n) => {
+            buf.push(tag::REPLACE_WITH_PREV);
+            write_usize(buf, *n);
+        }
+        TransformRule::DuplicateFirstBlock(n) => {
+            buf.push(tag::DUPLICATE_FIRST_BLOCK);
+            write_usize(buf, *n);
+        }
+        TransformRule::DuplicateLastBlock(n) => {
+            buf.push(tag::DUPLICATE_LAST_BLOCK);
+            write_usize(buf, *n);
+        }
+        TransformRule::TitleCase => buf.push(tag::TITLE_CASE),
+        TransformRule::AlternatingWordCase => buf.push(tag::ALTERNATING_WORD_CASE),
+        TransformRule::RegexReplace(pattern, replacement) => {
+            buf.push(tag::REGEX_REPLACE);
+            write_string(buf, pattern);
+            write_string(buf, replacement);
+        }
+        TransformRule::ToCase(case) => {
+            buf.push(tag::TO_CASE);
+            buf.push(match case {
+                Case::Title => tag::CASE_TITLE,
+                Case::Camel => tag::CASE_CAMEL,
+                Case::Pascal => tag::CASE_PASCAL,
+                Case::Snake => tag::CASE_SNAKE,
+                Case::Kebab => tag::CASE_KEBAB,
+                Case::ScreamingSnake => tag::CASE_SCREAMING_SNAKE,
+            });
+        }
+        TransformRule::CasePermute(limit) => {
+            buf.push(tag::CASE_PERMUTE);
+            write_usize(buf, *limit);
+        }
+        TransformRule::LeetReplace(table) => {
+            buf.push(tag::LEET_REPLACE);
+            write_usize(buf, table.len());
+            for (from, to) in table {
+                write_char(buf, *from);
+                write_chars(buf, to);
+            }
+        }
+    }
+}
+
+fn read_transform(opcode: u8, buf: &[u8], pos: &mut usize) -> Result<TransformRule, String> {
+    match opcode {
+        tag::LOWERCASE => Ok(TransformRule::Lowercase),
+        tag::UPPERCASE => Ok(TransformRule::Uppercase),
+        tag::CAPITALIZE => Ok(TransformRule::Capitalize),
+        tag::INVERT_CAPITALIZE => Ok(TransformRule::InvertCapitalize),
+        tag::TOGGLE_CASE => Ok(TransformRule::ToggleCase(read_option_usize(buf, pos)?)),
+        tag::REVERSE => Ok(TransformRule::Reverse),
+        tag::DUPLICATE => Ok(TransformRule::Duplicate(read_option_usize(buf, pos)?)),
+        tag::REFLECT => Ok(TransformRule::Reflect),
+        tag::ROTATE => {
+            let byte = *buf.get(*pos).ok_or("unexpected end of program while reading a rotation")?;
+            *pos += 1;
+            match byte {
+                tag::ROTATE_LEFT => Ok(TransformRule::Rotate(Rotation::Left)),
+                tag::ROTATE_RIGHT => Ok(TransformRule::Rotate(Rotation::Right)),
+                other => Err(format!("unknown rotation tag {other:#04x}")),
+            }
+        }
+        tag::APPEND => Ok(TransformRule::Append(read_string(buf, pos)?)),
+        tag::PREPEND => Ok(TransformRule::Prepend(read_string(buf, pos)?)),
+        tag::DELETE => Ok(TransformRule::Delete(read_usize(buf, pos)?)),
+        tag::EXTRACT => Ok(TransformRule::Extract(read_usize(buf, pos)?, read_usize(buf, pos)?)),
+        tag::OMIT => Ok(TransformRule::Omit(read_usize(buf, pos)?, read_usize(buf, pos)?)),
+        tag::INSERT => {
+            let n = read_usize(buf, pos)?;
+            Ok(TransformRule::Insert(n, read_string(buf, pos)?))
+        }
+        tag::OVERWRITE => {
+            let n = read_usize(buf, pos)?;
+            Ok(TransformRule::Overwrite(n, read_string(buf, pos)?))
+        }
+        tag::TRUNCATE => {
+            let byte = *buf.get(*pos).ok_or("unexpected end of program while reading a truncate mode")?;
+            *pos += 1;
+            match byte {
+                tag::TRUNCATE_LEFT => Ok(TransformRule::Truncate(Truncate::Left)),
+                tag::TRUNCATE_RIGHT => Ok(TransformRule::Truncate(Truncate::Right)),
+                tag::TRUNCATE_TO => Ok(TransformRule::Truncate(Truncate::To(read_usize(buf, pos)?))),
+                other => Err(format!("unknown truncate tag {other:#04x}")),
+            }
+        }
+        tag::REPLACE => Ok(TransformRule::Replace(read_string(buf, pos)?, read_string(buf, pos)?)),
+        tag::PURGE => Ok(TransformRule::Purge(read_string(buf, pos)?)),
+        tag::DUPLICATE_FIRST => Ok(TransformRule::DuplicateFirst(read_usize(buf, pos)?)),
+        tag::DUPLICATE_LAST => Ok(TransformRule::DuplicateLast(read_usize(buf, pos)?)),
+        tag::DUPLICATE_ALL => Ok(TransformRule::DuplicateAll),
+        tag::SWAP_FRONT => Ok(TransformRule::SwapFront),
+        tag::SWAP_BACK => Ok(TransformRule::SwapBack),
+        tag::SWAP => Ok(TransformRule::Swap(read_usize(buf, pos)?, read_usize(buf, pos)?)),
+        tag::SHIFT_LEFT => Ok(TransformRule::BitwiseShiftLeft(read_usize(buf, pos)?)),
+        tag::SHIFT_RIGHT => Ok(TransformRule::BitwiseShiftRight(read_usize(buf, pos)?)),
+        tag::ASCII_INCREMENT => Ok(TransformRule::AsciiIncrement(read_usize(buf, pos)?)),
+        tag::ASCII_DECREMENT => Ok(TransformRule::AsciiDecrement(read_usize(buf, pos)?)),
+        tag::REPLACE_WITH_NEXT => Ok(TransformRule::ReplaceWithNext(read_usize(buf, pos)?)),
+        tag::REPLACE_WITH_PREV => Ok(TransformRule::ReplaceWithPrev(read_usize(buf, pos)?)),
+        tag::DUPLICATE_FIRST_BLOCK => Ok(TransformRule::DuplicateFirstBlock(read_usize(buf, pos)?)),
+        tag::DUPLICATE_LAST_BLOCK => Ok(TransformRule::DuplicateLastBlock(read_usize(buf, pos)?)),
+        tag::TITLE_CASE => Ok(TransformRule::TitleCase),
+        tag::ALTERNATING_WORD_CASE => Ok(TransformRule::AlternatingWordCase),
+        tag::REGEX_REPLACE => {
+            let pattern = read_string(buf, pos)?;
+            Ok(TransformRule::RegexReplace(pattern, read_string(buf, pos)?))
+        }
+        tag::TO_CASE => {
+            let byte = *buf.get(*pos).ok_or("unexpected end of program while reading a case style")?;
+            *pos += 1;
+            let case = match byte {
+                tag::CASE_TITLE => Case::Title,
+                tag::CASE_CAMEL => Case::Camel,
+                tag::CASE_PASCAL => Case::Pascal,
+                tag::CASE_SNAKE => Case::Snake,
+                tag::CASE_KEBAB => Case::Kebab,
+                tag::CASE_SCREAMING_SNAKE => Case::ScreamingSnake,
+                other => return Err(format!("unknown case style tag {other:#04x}")),
+            };
+            Ok(TransformRule::ToCase(case))
+        }
+        tag::CASE_PERMUTE => Ok(TransformRule::CasePermute(read_usize(buf, pos)?)),
+        tag::LEET_REPLACE => {
+            let len = read_usize(buf, pos)?;
+            let table = (0..len)
+                .map(|_| -> Result<(char, Vec<char>), String> {
+                    let from = read_char(buf, pos)?;
+                    Ok((from, read_chars(buf, pos)?))
+                })
+                .collect::<Result<_, _>>()?;
+            Ok(TransformRule::LeetReplace(table))
+        }
+        other => Err(format!("unknown transform opcode {other:#04x}")),
+    }
+}
+
+fn write_reject(buf: &mut Vec<u8>, rule: &RejectRule) {
+    match rule {
+        RejectRule::ShorterThan(n) => {
+            buf.push(tag::SHORTER_THAN);
+            write_usize(buf, *n);
+        }
+        RejectRule::LongerThan(n) => {
+            buf.push(tag::LONGER_THAN);
+            write_usize(buf, *n);
+        }
+        RejectRule::NotEqualTo(n) => {
+            buf.push(tag::NOT_EQUAL_TO);
+            write_usize(buf, *n);
+        }
+        RejectRule::Contains(s) => {
+            buf.push(tag::CONTAINS);
+            write_string(buf, s);
+        }
+        RejectRule::NotContains(s) => {
+            buf.push(tag::NOT_CONTAINS);
+            write_string(buf, s);
+        }
+        RejectRule::NotStartsWith(s) => {
+            buf.push(tag::NOT_STARTS_WITH);
+            write_string(buf, s);
+        }
+        RejectRule::NotEndsWith(s) => {
+            buf.push(tag::NOT_ENDS_WITH);
+            write_string(buf, s);
+        }
+        RejectRule::NotEqualAt(n, s) => {
+            buf.push(tag::NOT_EQUAL_AT);
+            write_usize(buf, *n);
+            write_string(buf, s);
+        }
+        RejectRule::ContainsLessThan(n, s) => {
+            buf.push(tag::CONTAINS_LESS_THAN);
+            write_usize(buf, *n);
+            write_string(buf, s);
+        }
+        RejectRule::RegexMatch(pattern) => {
+            buf.push(tag::REGEX_MATCH);
+            write_string(buf, pattern);
+        }
+        RejectRule::RegexNotMatch(pattern) => {
+            buf.push(tag::REGEX_NOT_MATCH);
+            write_string(buf, pattern);
+        }
+        RejectRule::RequiresClass(class) => {
+            buf.push(tag::REQUIRES_CLASS);
+            write_char_class(buf, class);
+        }
+        RejectRule::AllowedOnly(classes) => {
+            buf.push(tag::ALLOWED_ONLY);
+            write_usize(buf, classes.len());
+            for class in classes {
+                write_char_class(buf, class);
+            }
+        }
+    }
+}
+
+fn read_reject(opcode: u8, buf: &[u8], pos: &mut usize) -> Result<RejectRule, String> {
+    match opcode {
+        tag::SHORTER_THAN => Ok(RejectRule::ShorterThan(read_usize(buf, pos)?)),
+        tag::LONGER_THAN => Ok(RejectRule::LongerThan(read_usize(buf, pos)?)),
+        tag::NOT_EQUAL_TO => Ok(RejectRule::NotEqualTo(read_usize(buf, pos)?)),
+        tag::CONTAINS => Ok(RejectRule::Contains(read_string(buf, pos)?)),
+        tag::NOT_CONTAINS => Ok(RejectRule::NotContains(read_string(buf, pos)?)),
+        tag::NOT_STARTS_WITH => Ok(RejectRule::NotStartsWith(read_string(buf, pos)?)),
+        tag::NOT_ENDS_WITH => Ok(RejectRule::NotEndsWith(read_string(buf, pos)?)),
+        tag::NOT_EQUAL_AT => {
+            let n = read_usize(buf, pos)?;
+            Ok(RejectRule::NotEqualAt(n, read_string(buf, pos)?))
+        }
+        tag::CONTAINS_LESS_THAN => {
+            let n = read_usize(buf, pos)?;
+            Ok(RejectRule::ContainsLessThan(n, read_string(buf, pos)?))
+        }
+        tag::REGEX_MATCH => Ok(RejectRule::RegexMatch(read_string(buf, pos)?)),
+        tag::REGEX_NOT_MATCH => Ok(RejectRule::RegexNotMatch(read_string(buf, pos)?)),
+        tag::REQUIRES_CLASS => Ok(RejectRule::RequiresClass(read_char_class(buf, pos)?)),
+        tag::ALLOWED_ONLY => {
+            let len = read_usize(buf, pos)?;
+            let classes = (0..len).map(|_| read_char_class(buf, pos)).collect::<Result<_, _>>()?;
+            Ok(RejectRule::AllowedOnly(classes))
+        }
+        other => Err(format!("unknown reject opcode {other:#04x}")),
+    }
+}
+
+fn write_rule(buf: &mut Vec<u8>, rule: &Rule) {
+    match rule {
+        Rule::NoOp => buf.push(tag::NO_OP),
+        Rule::End => buf.push(tag::END),
+        Rule::Transform(transform) => write_transform(buf, transform),
+        Rule::Reject(reject) => write_reject(buf, reject),
+    }
+}
+
+fn read_rule(buf: &[u8], pos: &mut usize) -> Result<Rule, String> {
+    let opcode = *buf.get(*pos).ok_or("unexpected end of program while reading an opcode")?;
+    *pos += 1;
+    match opcode {
+        tag::NO_OP => Ok(Rule::NoOp),
+        tag::END => Ok(Rule::End),
+        tag::LOWERCASE..=tag::LEET_REPLACE => read_transform(opcode, buf, pos).map(Rule::Transform),
+        tag::SHORTER_THAN..=tag::ALLOWED_ONLY => read_reject(opcode, buf, pos).map(Rule::Reject),
+        other => Err(format!("unknown opcode {other:#04x}")),
+    }
+}
+
+/// Parses, simplifies, and encodes each rule line in `rules` into a single
+/// compact binary program that [`run_compiled`] can decode and run without
+/// re-parsing.
+pub fn compile(rules: &[String]) -> Result<Vec<u8>, String> {
+    let mut buf = Vec::new();
+    write_usize(&mut buf, rules.len());
+    for rule_line in rules {
+        let mut line = rule_line.as_str();
+        let parsed = parse_line(&mut line).map_err(|e| e.to_string())?;
+        let simplified = Rule::simplify(parsed);
+        write_usize(&mut buf, simplified.len());
+        for rule in &simplified {
+            write_rule(&mut buf, rule);
+        }
+    }
+    Ok(buf)
+}
+
+fn decode(program: &[u8]) -> Result<Vec<Vec<Rule>>, String> {
+    let mut pos = 0;
+    let num_lines = read_usize(program, &mut pos)?;
+    let mut lines = Vec::with_capacity(num_lines);
+    for _ in 0..num_lines {
+        let num_rules = read_usize(program, &mut pos)?;
+        let mut rules = Vec::with_capacity(num_rules);
+        for _ in 0..num_rules {
+            rules.push(read_rule(program, &mut pos)?);
+        }
+        lines.push(rules);
+    }
+    Ok(lines)
+}
+
+/// Decodes a program produced by [`compile`] and runs it over `words`, the
+/// same way [`crate::engine::run`] would over the original rule-line strings.
+pub fn run_compiled(program: &[u8], words: Vec<String>) -> Result<Vec<String>, String> {
+    let lines = decode(program)?;
+    engine::run_lines(&lines, &words, RuleMode::Bytes)
+}
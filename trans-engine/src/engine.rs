@@ -1,34 +1,304 @@
+use std::borrow::Cow;
+use std::collections::HashSet;
+use std::io::BufRead;
+use std::sync::mpsc;
+
 use rayon::prelude::*;
+use regex::Regex;
+
+use crate::parser::{parse_line_mode, ParseError, ParseMode};
+use crate::lang::{Rule, RuleMode, TransformRule, RejectRule};
+use crate::policy::compile_policy;
+
+/// A `Rule` with any embedded regex patterns pre-compiled, so the pattern is
+/// compiled once per rule line instead of once per word.
+enum CompiledRule<'a> {
+    Plain(&'a Rule),
+    RegexReplace(Regex, &'a str),
+    RegexMatch(Regex),
+    RegexNotMatch(Regex),
+}
 
-use crate::parser::parse_line;
-use crate::lang::Rule;
+fn compile_rules(rules: &[Rule]) -> Result<Vec<CompiledRule>, String> {
+    rules
+        .iter()
+        .map(|rule| match rule {
+            Rule::Transform(TransformRule::RegexReplace(pattern, replacement)) => {
+                Regex::new(pattern)
+                    .map(|re| CompiledRule::RegexReplace(re, replacement.as_str()))
+                    .map_err(|e| format!("invalid regex `{}`: {}", pattern, e))
+            }
+            Rule::Reject(RejectRule::RegexMatch(pattern)) => {
+                Regex::new(pattern)
+                    .map(CompiledRule::RegexMatch)
+                    .map_err(|e| format!("invalid regex `{}`: {}", pattern, e))
+            }
+            Rule::Reject(RejectRule::RegexNotMatch(pattern)) => {
+                Regex::new(pattern)
+                    .map(CompiledRule::RegexNotMatch)
+                    .map_err(|e| format!("invalid regex `{}`: {}", pattern, e))
+            }
+            other => Ok(CompiledRule::Plain(other)),
+        })
+        .collect()
+}
+
+/// Runs `rules` over `input`, keeping the result borrowed as long as no rule
+/// changes it, so a reject-only chain returns the word without copying it.
+/// A plain transform builds a temporary `String` even when it leaves the word
+/// unchanged, and a `~s` regex replacement always yields an owned copy.
+fn run_compiled<'a>(rules: &[CompiledRule], input: &'a str, mode: RuleMode) -> Option<Cow<'a, str>> {
+    let mut output = Cow::Borrowed(input);
+    for rule in rules {
+        output = match rule {
+            CompiledRule::Plain(rule) => rule.run_cow_mode(output, mode)?,
+            CompiledRule::RegexReplace(re, replacement) => {
+                Cow::Owned(re.replace_all(&output, *replacement).into_owned())
+            }
+            CompiledRule::RegexMatch(re) => {
+                if re.is_match(&output) {
+                    return None;
+                }
+                output
+            }
+            CompiledRule::RegexNotMatch(re) => {
+                if !re.is_match(&output) {
+                    return None;
+                }
+                output
+            }
+        };
+    }
+    Some(output)
+}
 
 pub fn run(rules: Vec<String>, words: Vec<String>) -> Result<Vec<String>, String> {
-    // We can call py.allow_threads to ensure the GIL is released during our
-    // operations
-    // This example just wraps `arrow_select::take::take`
-    let mut output_array: Vec<String> = Vec::new();
+    run_mode(rules, words, ParseMode::Strict)
+}
+
+/// Like [`run`], but lets the caller select [`ParseMode::Verbose`] to parse
+/// rule lines that use insignificant whitespace and `#` inline comments.
+pub fn run_mode(rules: Vec<String>, words: Vec<String>, mode: ParseMode) -> Result<Vec<String>, String> {
+    let (output, _errors) = run_collecting(rules, words, mode)?;
+    Ok(output)
+}
+
+/// Like [`run_mode`], but runs with [`RuleMode::Unicode`] instead of the
+/// default [`RuleMode::Bytes`] - use this for accented or otherwise
+/// non-ASCII wordlists, where byte-level semantics would mis-measure word
+/// lengths and mangle multibyte characters under `L`/`R`/`+`/`-`.
+pub fn run_unicode(rules: Vec<String>, words: Vec<String>, mode: ParseMode) -> Result<Vec<String>, String> {
+    let (output, _errors) = run_collecting_rule_mode(rules, words, mode, RuleMode::Unicode)?;
+    Ok(output)
+}
+
+/// Like [`run_mode`], but returns the [`ParseError`]s for any rule lines that
+/// failed to parse alongside the output, instead of silently dropping them.
+/// Lines that do parse are still applied; a bad line just contributes no
+/// output and an entry in the returned error list.
+pub fn run_collecting(
+    rules: Vec<String>,
+    words: Vec<String>,
+    mode: ParseMode,
+) -> Result<(Vec<String>, Vec<ParseError>), String> {
+    run_collecting_rule_mode(rules, words, mode, RuleMode::Bytes)
+}
+
+/// Like [`run_collecting`], but lets the caller select [`RuleMode::Unicode`]
+/// so length/index-based reject rules count codepoints and byte-oriented
+/// transforms (`L`/`R`/`+`/`-`) operate on whole codepoints, instead of
+/// [`RuleMode::Bytes`]'s default raw-byte semantics.
+pub fn run_collecting_rule_mode(
+    rules: Vec<String>,
+    words: Vec<String>,
+    mode: ParseMode,
+    rule_mode: RuleMode,
+) -> Result<(Vec<String>, Vec<ParseError>), String> {
+    let mut lines: Vec<Vec<Rule>> = Vec::new();
+    let mut errors: Vec<ParseError> = Vec::new();
     for element in rules.iter() {
-        match parse_line(&mut element.to_string().as_str()) {
-            Ok(parsed_rules) => {
+        match parse_line_mode(&mut element.to_string().as_str(), mode) {
+            Ok(parsed_rules) => lines.push(Rule::simplify(parsed_rules)),
+            Err(e) => errors.push(e),
+        }
+    }
+
+    let output_array = run_lines(&lines, &words, rule_mode)?;
+    Ok((output_array, errors))
+}
+
+/// Whether `rule` is a fan-out rule ([`TransformRule::CasePermute`]/
+/// [`TransformRule::LeetReplace`]) - one only [`Rule::run_many`]/
+/// [`Rule::run_all_many`] expand into more than one candidate per input.
+fn is_fan_out_rule(rule: &Rule) -> bool {
+    matches!(rule, Rule::Transform(TransformRule::CasePermute(_) | TransformRule::LeetReplace(_)))
+}
 
-                let rules_slice = &Rule::simplify(parsed_rules)[..];
+/// Runs already-parsed, already-simplified rule lines over `words`, applying
+/// each line to every word in turn - the shared tail end of [`run_collecting`]
+/// once parsing is out of the way. [`crate::codec`] calls this directly so a
+/// decoded binary program never has to be re-parsed.
+pub(crate) fn run_lines(lines: &[Vec<Rule>], words: &[String], mode: RuleMode) -> Result<Vec<String>, String> {
+    let mut output_array: Vec<String> = Vec::new();
+    for simplified in lines {
+        // A line containing a fan-out rule needs every candidate it produces,
+        // not just the first; route it through `run_all_many` instead of the
+        // single-output `run_compiled` pipeline used by every other line.
+        if simplified.iter().any(is_fan_out_rule) {
+            let fan_out_results: Vec<String> = words
+                .par_iter()
+                .flat_map_iter(|word| Rule::run_all_many_mode(simplified, word, mode))
+                .collect();
+            output_array.extend(fan_out_results);
+            continue;
+        }
 
-                // Parallel processing of words
-                let thread_results: Vec<String> = words.par_iter()
-                    .filter_map(|values| {
-                        // perf issue: clone is expensive
-                        Rule::run_all(rules_slice, values.to_string())
-                    })
-                    .collect();
+        // An invalid regex in one line (e.g. a malformed `~s`/`~m`/`~M`
+        // pattern) shouldn't take down every other line's output with it -
+        // skip just this line, the same way a line that fails to parse only
+        // costs its own contribution to `output_array`.
+        let compiled = match compile_rules(simplified) {
+            Ok(compiled) => compiled,
+            Err(_) => continue,
+        };
 
-                output_array.extend(thread_results);
+        // Rejected words are dropped without being copied; every surviving
+        // word ends up owned in the output, copied once by `into_owned` unless
+        // a transform in `run_compiled` already produced an owned copy.
+        let thread_results: Vec<String> = words.par_iter()
+            .filter_map(|word| run_compiled(&compiled, word.as_str(), mode))
+            .map(Cow::into_owned)
+            .collect();
+
+        output_array.extend(thread_results);
+    }
+
+    Ok(output_array)
+}
+
+/// How multiple rulesets are composed together by [`run_combining`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Combine {
+    /// Every ruleset runs independently against the original `words` and the
+    /// outputs are concatenated - the set union of what each ruleset alone
+    /// would produce. This is what [`run`] does for its single ruleset.
+    Union,
+    /// Rulesets run in sequence, each one against the previous ruleset's
+    /// output (the first against the original `words`), so later rulesets
+    /// see candidates already mangled by earlier ones - the cross-product
+    /// expansion hashcat achieves by chaining multiple `-r` files.
+    Chain,
+}
+
+/// Runs `rulesets` over `words`, composing them according to `combine`. Each
+/// stage reuses [`run_mode`]'s parallel `par_iter` pipeline. When `dedup` is
+/// set, every stage's output is deduplicated (order-preserving) before being
+/// handed to the next stage or returned, which bounds the combinatorial
+/// blow-up `Combine::Chain` can otherwise cause.
+pub fn run_combining(
+    rulesets: Vec<Vec<String>>,
+    words: Vec<String>,
+    mode: ParseMode,
+    combine: Combine,
+    dedup: bool,
+) -> Result<Vec<String>, String> {
+    match combine {
+        Combine::Union => {
+            let mut output = Vec::new();
+            for ruleset in &rulesets {
+                output.extend(run_mode(ruleset.clone(), words.clone(), mode)?);
             }
-            Err(e) => {
-                println!("Error: {}", e);
+            Ok(if dedup { dedup_stable(output) } else { output })
+        }
+        Combine::Chain => {
+            let mut current = words;
+            for ruleset in &rulesets {
+                current = run_mode(ruleset.clone(), current, mode)?;
+                if dedup {
+                    current = dedup_stable(current);
+                }
             }
+            Ok(current)
         }
     }
-    
-    Ok(output_array)
-}
\ No newline at end of file
+}
+
+/// Removes duplicate words, keeping the first occurrence of each.
+fn dedup_stable(words: Vec<String>) -> Vec<String> {
+    let mut seen = HashSet::new();
+    words.into_iter().filter(|word| seen.insert(word.clone())).collect()
+}
+
+/// Filters `words` down to those satisfying the password-policy `spec`
+/// (see [`crate::policy::compile_policy`] for the specification grammar).
+pub fn filter_by_policy(spec: &str, words: Vec<String>) -> Result<Vec<String>, String> {
+    let rules = compile_policy(spec).map_err(|e| e.to_string())?;
+    let compiled = compile_rules(&rules)?;
+
+    // Policy filtering is reject-only, so surviving words are never rewritten
+    // and this never allocates beyond the final `into_owned` for the output.
+    Ok(words.par_iter()
+        .filter_map(|word| run_compiled(&compiled, word.as_str(), RuleMode::Bytes))
+        .map(Cow::into_owned)
+        .collect())
+}
+
+/// Like [`run_mode`], but never materializes `words` or the output in memory:
+/// `words` is consumed lazily and the result is a lazy iterator, so a caller
+/// can pipe a multi-GB dictionary straight through with bounded memory
+/// (e.g. `stdin` -> rules -> `stdout`, one line at a time).
+///
+/// Rule lines are parsed and validated up front (there are usually only a
+/// handful of them, unlike the wordlist), then every surviving word is run
+/// against all of them in parallel via rayon's `par_bridge`. Because `words`
+/// can only be iterated once, output here is grouped by word rather than by
+/// rule line as in [`run_mode`]: for each word, every rule line's result
+/// (that isn't rejected) is emitted before moving to the next word.
+pub fn run_streaming(
+    rules: Vec<String>,
+    words: impl Iterator<Item = String> + Send + 'static,
+    mode: ParseMode,
+) -> Result<impl Iterator<Item = String>, String> {
+    let mut lines: Vec<Vec<Rule>> = Vec::new();
+    for element in rules.iter() {
+        if let Ok(parsed_rules) = parse_line_mode(&mut element.to_string().as_str(), mode) {
+            let simplified = Rule::simplify(parsed_rules);
+            compile_rules(&simplified)?; // validate regex patterns eagerly
+            lines.push(simplified);
+        }
+    }
+
+    let (sender, receiver) = mpsc::sync_channel::<String>(1024);
+
+    std::thread::spawn(move || {
+        let compiled: Vec<Vec<CompiledRule>> = lines
+            .iter()
+            .map(|simplified| compile_rules(simplified).expect("regex patterns validated above"))
+            .collect();
+
+        words.par_bridge().for_each(|word| {
+            for (simplified, rule_line) in lines.iter().zip(&compiled) {
+                if simplified.iter().any(is_fan_out_rule) {
+                    for result in Rule::run_all_many_mode(simplified, &word, RuleMode::Bytes) {
+                        let _ = sender.send(result);
+                    }
+                } else if let Some(result) = run_compiled(rule_line, &word, RuleMode::Bytes) {
+                    let _ = sender.send(result.into_owned());
+                }
+            }
+        });
+    });
+
+    Ok(receiver.into_iter())
+}
+
+/// Convenience wrapper around [`run_streaming`] that reads words one per
+/// line from any [`BufRead`] (typically `stdin`), skipping lines that fail
+/// to read.
+pub fn run_streaming_reader<R: BufRead + Send + 'static>(
+    rules: Vec<String>,
+    reader: R,
+    mode: ParseMode,
+) -> Result<impl Iterator<Item = String>, String> {
+    run_streaming(rules, reader.lines().map_while(Result::ok), mode)
+}
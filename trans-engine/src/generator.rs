@@ -0,0 +1,185 @@
+//! Readable-passphrase generator.
+//!
+//! Unlike the rule engine, which mutates an existing wordlist, this module
+//! extracts words from free-form input text and assembles new candidates
+//! from them - a seed-generation counterpart that can be piped straight into
+//! [`crate::engine::run`] for further rule-based mutation.
+
+use rand::Rng;
+use rand::seq::SliceRandom;
+use std::collections::HashSet;
+
+/// How each selected word's case is mutated before assembly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CasePolicy {
+    CapitalizeFirst,
+    Lowercase,
+    Uppercase,
+    RandomToggle,
+}
+
+/// Where digit/special characters may be inserted into an assembled candidate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InsertionPolicy {
+    /// Any character offset in the assembled candidate.
+    Uniform,
+    /// Only at the boundaries between words, including start and end.
+    WordBoundaryOnly,
+}
+
+/// Tunables for [`generate`].
+#[derive(Debug, Clone)]
+pub struct GenConfig {
+    pub min_word_length: usize,
+    pub candidate_count: usize,
+    pub target_min_length: usize,
+    pub target_max_length: usize,
+    pub num_digits: usize,
+    pub num_specials: usize,
+    /// Keep standalone number tokens even if shorter than `min_word_length`.
+    pub keep_numbers: bool,
+    pub case_policy: CasePolicy,
+    pub insertion: InsertionPolicy,
+}
+
+impl Default for GenConfig {
+    fn default() -> Self {
+        GenConfig {
+            min_word_length: 3,
+            candidate_count: 100,
+            target_min_length: 8,
+            target_max_length: 16,
+            num_digits: 1,
+            num_specials: 1,
+            keep_numbers: true,
+            case_policy: CasePolicy::CapitalizeFirst,
+            insertion: InsertionPolicy::WordBoundaryOnly,
+        }
+    }
+}
+
+const SPECIAL_CHARS: &[char] = &['!', '@', '#', '$', '%', '^', '&', '*', '?'];
+
+fn tokenize(text: &str, config: &GenConfig) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|token| !token.is_empty())
+        .filter(|token| {
+            let is_number = token.chars().all(|c| c.is_ascii_digit());
+            token.chars().count() >= config.min_word_length || (config.keep_numbers && is_number)
+        })
+        .map(|token| token.to_string())
+        .collect()
+}
+
+fn apply_case(word: &str, policy: CasePolicy, rng: &mut impl Rng) -> String {
+    match policy {
+        CasePolicy::Lowercase => word.to_lowercase(),
+        CasePolicy::Uppercase => word.to_uppercase(),
+        CasePolicy::CapitalizeFirst => {
+            let mut chars = word.chars();
+            match chars.next() {
+                None => String::new(),
+                Some(first) => first.to_uppercase().collect::<String>() + chars.as_str().to_lowercase().as_str(),
+            }
+        }
+        CasePolicy::RandomToggle => word
+            .chars()
+            .map(|c| if rng.gen_bool(0.5) { c.to_ascii_uppercase() } else { c.to_ascii_lowercase() })
+            .collect(),
+    }
+}
+
+/// Picks random words (with case applied) until the target length range is
+/// reached, or there's nothing left worth adding.
+fn assemble_words(words: &[String], config: &GenConfig, rng: &mut impl Rng) -> Vec<String> {
+    let mut assembled: Vec<String> = Vec::new();
+    let mut len = 0;
+    while len < config.target_max_length {
+        let Some(word) = words.choose(rng) else { break };
+        let cased = apply_case(word, config.case_policy, rng);
+        let next_len = len + cased.chars().count();
+        if next_len > config.target_max_length && len >= config.target_min_length {
+            break;
+        }
+        len = next_len;
+        assembled.push(cased);
+        if len >= config.target_min_length && rng.gen_bool(0.3) {
+            break;
+        }
+    }
+    assembled
+}
+
+/// Inserts the configured count of digits and special characters into the
+/// assembled word segments, per `config.insertion`.
+fn insert_padding(segments: Vec<String>, config: &GenConfig, rng: &mut impl Rng) -> String {
+    let mut pool: Vec<char> = Vec::with_capacity(config.num_digits + config.num_specials);
+    for _ in 0..config.num_digits {
+        pool.push((b'0' + rng.gen_range(0..10)) as char);
+    }
+    for _ in 0..config.num_specials {
+        pool.push(*SPECIAL_CHARS.choose(rng).unwrap());
+    }
+
+    match config.insertion {
+        InsertionPolicy::WordBoundaryOnly => {
+            let mut slots = segments;
+            for c in pool {
+                let pos = rng.gen_range(0..=slots.len());
+                slots.insert(pos, c.to_string());
+            }
+            slots.concat()
+        }
+        InsertionPolicy::Uniform => {
+            let mut candidate: String = segments.concat();
+            for c in pool {
+                let char_count = candidate.chars().count();
+                let pos = rng.gen_range(0..=char_count);
+                let byte_pos = candidate.char_indices().nth(pos).map(|(i, _)| i).unwrap_or(candidate.len());
+                candidate.insert(byte_pos, c);
+            }
+            candidate
+        }
+    }
+}
+
+/// Generates up to `config.candidate_count` distinct readable password
+/// candidates from the words found in `text`.
+///
+/// # Examples
+///
+/// ```
+/// # use crate::_core::generator::{generate, GenConfig};
+/// let config = GenConfig { candidate_count: 5, num_digits: 0, num_specials: 0, ..GenConfig::default() };
+/// let candidates = generate("the quick brown fox jumps", &config);
+/// assert!(!candidates.is_empty());
+/// ```
+pub fn generate(text: &str, config: &GenConfig) -> Vec<String> {
+    let words = tokenize(text, config);
+    if words.is_empty() {
+        return Vec::new();
+    }
+
+    let mut rng = rand::thread_rng();
+    let mut seen = HashSet::new();
+    let mut candidates = Vec::new();
+
+    // Bound attempts so low-diversity input can't loop forever chasing
+    // `candidate_count` distinct candidates.
+    let max_attempts = config.candidate_count.saturating_mul(10).max(1);
+    for _ in 0..max_attempts {
+        if candidates.len() >= config.candidate_count {
+            break;
+        }
+        let segments = assemble_words(&words, config, &mut rng);
+        if segments.is_empty() {
+            continue;
+        }
+        let candidate = insert_padding(segments, config, &mut rng);
+        if seen.insert(candidate.clone()) {
+            candidates.push(candidate);
+        }
+    }
+
+    candidates
+}
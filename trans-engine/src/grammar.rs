@@ -0,0 +1,120 @@
+//! Declarative `rust-peg` grammar for the built-in rule-language operators.
+//!
+//! Each opcode is one grammar production instead of a branch in a
+//! hand-written dispatch table, so adding or reading an operator's shape is a
+//! one-line diff. [`crate::parser::parse_rule`] tries [`rules::op`] for every
+//! operator right after checking for a `#` comment, and only falls back to
+//! hand-written parsing for the
+//! regex rules (their escape-aware delimited fields aren't expressible as a
+//! plain peg literal) and for operators registered via
+//! [`crate::parser::register_operator_extension`].
+
+use crate::lang::{Case, Rotation, Rule, RejectRule, TransformRule, Truncate};
+
+peg::parser! {
+    pub grammar rules() for str {
+        rule number() -> usize = n:$(['0'..='9']+) { n.parse().unwrap() }
+
+        /// `N:M`, or exactly two raw digits (`NM`) with no separator - matching
+        /// the rule language's historical leniency for single-digit ranges.
+        rule range() -> (usize, usize)
+            = a:number() ":" b:number() { (a, b) }
+            / a:['0'..='9'] b:['0'..='9'] {
+                (a.to_digit(10).unwrap() as usize, b.to_digit(10).unwrap() as usize)
+            }
+
+        rule char_param() -> String = c:[_] { c.to_string() }
+
+        /// One built-in rule-language operator, consuming its own parameters.
+        /// Returns the parsed `Rule` along with how many bytes it consumed.
+        ///
+        /// `peg`'s generated entry points require the whole input they're
+        /// given to match, so the trailing `[_]*` soaks up whatever comes
+        /// after this single operator (further operators, a newline, ...)
+        /// without being part of the match itself - only `start`/`end`
+        /// (captured before that wildcard runs) determine `bytes_consumed`.
+        pub rule op() -> (Rule, usize)
+            = start:position!() r:op_body() end:position!() [_]* { (r, end - start) }
+
+        rule op_body() -> Rule
+            = ":" { Rule::NoOp }
+            / "l" { Rule::Transform(TransformRule::Lowercase) }
+            / "u" { Rule::Transform(TransformRule::Uppercase) }
+            / "c" { Rule::Transform(TransformRule::Capitalize) }
+            / "C" { Rule::Transform(TransformRule::InvertCapitalize) }
+            / "t" { Rule::Transform(TransformRule::ToggleCase(None)) }
+            / "T" n:number() { Rule::Transform(TransformRule::ToggleCase(Some(n))) }
+            / "r" { Rule::Transform(TransformRule::Reverse) }
+            / "d" { Rule::Transform(TransformRule::Duplicate(None)) }
+            / "p" n:number() { Rule::Transform(TransformRule::Duplicate(Some(n))) }
+            / "f" { Rule::Transform(TransformRule::Reflect) }
+            / "{" { Rule::Transform(TransformRule::Rotate(Rotation::Left)) }
+            / "}" { Rule::Transform(TransformRule::Rotate(Rotation::Right)) }
+            / "$" c:char_param() { Rule::Transform(TransformRule::Append(c)) }
+            / "^" c:char_param() { Rule::Transform(TransformRule::Prepend(c)) }
+            / "[" { Rule::Transform(TransformRule::Truncate(Truncate::Left)) }
+            / "]" { Rule::Transform(TransformRule::Truncate(Truncate::Right)) }
+            / "D" n:number() { Rule::Transform(TransformRule::Delete(n)) }
+            / "x" r:range() { Rule::Transform(TransformRule::Extract(r.0, r.1)) }
+            / "O" r:range() { Rule::Transform(TransformRule::Omit(r.0, r.1)) }
+            / "i" c:char_param() n:number() { Rule::Transform(TransformRule::Insert(n, c)) }
+            / "o" n:number() c:char_param() { Rule::Transform(TransformRule::Overwrite(n, c)) }
+            / "'" n:number() { Rule::Transform(TransformRule::Truncate(Truncate::To(n))) }
+            / "s" a:char_param() b:char_param() { Rule::Transform(TransformRule::Replace(a, b)) }
+            / "@" c:char_param() { Rule::Transform(TransformRule::Purge(c)) }
+            / "z" n:number() { Rule::Transform(TransformRule::DuplicateFirst(n)) }
+            / "Z" n:number() { Rule::Transform(TransformRule::DuplicateLast(n)) }
+            / "q" { Rule::Transform(TransformRule::DuplicateAll) }
+            // hashcat-specific transformations
+            / "k" { Rule::Transform(TransformRule::SwapFront) }
+            / "K" { Rule::Transform(TransformRule::SwapBack) }
+            / "*" r:range() { Rule::Transform(TransformRule::Swap(r.0, r.1)) }
+            / "L" n:number() { Rule::Transform(TransformRule::BitwiseShiftLeft(n)) }
+            / "R" n:number() { Rule::Transform(TransformRule::BitwiseShiftRight(n)) }
+            / "+" n:number() { Rule::Transform(TransformRule::AsciiIncrement(n)) }
+            / "-" n:number() { Rule::Transform(TransformRule::AsciiDecrement(n)) }
+            / "." n:number() { Rule::Transform(TransformRule::ReplaceWithNext(n)) }
+            / "," n:number() { Rule::Transform(TransformRule::ReplaceWithPrev(n)) }
+            / "y" n:number() { Rule::Transform(TransformRule::DuplicateFirstBlock(n)) }
+            / "Y" n:number() { Rule::Transform(TransformRule::DuplicateLastBlock(n)) }
+            // reject rules
+            / "<" n:number() { Rule::Reject(RejectRule::LongerThan(n)) }
+            / ">" n:number() { Rule::Reject(RejectRule::ShorterThan(n)) }
+            / "_" n:number() { Rule::Reject(RejectRule::NotEqualTo(n)) }
+            / "!" c:char_param() { Rule::Reject(RejectRule::Contains(c)) }
+            / "/" c:char_param() { Rule::Reject(RejectRule::NotContains(c)) }
+            / "(" c:char_param() { Rule::Reject(RejectRule::NotStartsWith(c)) }
+            / ")" c:char_param() { Rule::Reject(RejectRule::NotEndsWith(c)) }
+            / "=" n:number() c:char_param() { Rule::Reject(RejectRule::NotEqualAt(n, c)) }
+            / "%" n:number() c:char_param() { Rule::Reject(RejectRule::ContainsLessThan(n, c)) }
+            // word-boundary-aware case conversion: ~cT (Title), ~cC (Camel), ~cP (Pascal),
+            // ~cS (Snake), ~cK (Kebab), ~cU (ScreamingSnake). The regex rules
+            // (~s, ~m, ~M) need escape-aware delimiters and stay hand-written
+            // in `parser::regex_op`.
+            / "~c" "T" { Rule::Transform(TransformRule::ToCase(Case::Title)) }
+            / "~c" "C" { Rule::Transform(TransformRule::ToCase(Case::Camel)) }
+            / "~c" "P" { Rule::Transform(TransformRule::ToCase(Case::Pascal)) }
+            / "~c" "S" { Rule::Transform(TransformRule::ToCase(Case::Snake)) }
+            / "~c" "K" { Rule::Transform(TransformRule::ToCase(Case::Kebab)) }
+            / "~c" "U" { Rule::Transform(TransformRule::ToCase(Case::ScreamingSnake)) }
+            // word-boundary-aware casing that doesn't fit the `Case` enum's
+            // separator-based styles: ~wT (TitleCase) and ~wA (AlternatingWordCase).
+            / "~w" "T" { Rule::Transform(TransformRule::TitleCase) }
+            / "~w" "A" { Rule::Transform(TransformRule::AlternatingWordCase) }
+            // fan-out rules (only expanded by TransformRule::run_many/
+            // Rule::run_all_many; TransformRule::run's "first variant" is the
+            // input unchanged): ~pN (CasePermute) and ~l<from><count><to...>+
+            // (LeetReplace) - each substitution is a source char followed by
+            // a single-digit replacement count and that many replacement
+            // chars, so substitutions can be told apart unambiguously
+            // without a delimiter. The count is a single digit (not
+            // `number()`'s greedy `['0'..='9']+`) because a greedy multi-digit
+            // count can't be told apart from a count followed by
+            // digit-looking replacement characters.
+            / "~p" n:number() { Rule::Transform(TransformRule::CasePermute(n)) }
+            / "~l" subs:leet_sub()+ { Rule::Transform(TransformRule::LeetReplace(subs)) }
+
+        rule leet_sub() -> (char, Vec<char>)
+            = from:[_] n:['0'..='9'] to:[_]*<{n.to_digit(10).unwrap() as usize}> { (from, to) }
+    }
+}
@@ -1,6 +1,28 @@
+use std::borrow::Cow;
+
 use itertools::Itertools;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::units::Units;
+
+/// Selects whether [`Rule::run`]/[`Rule::run_all`] treat a word as raw UTF-8
+/// bytes or as a sequence of Unicode scalar values. Byte-oriented transforms
+/// (`BitwiseShiftLeft`/`Right`, `AsciiIncrement`/`Decrement`) and the
+/// length-based reject rules (`ShorterThan`/`LongerThan`/`NotEqualTo`) read
+/// this to decide which units they
+/// operate over; every other rule behaves identically in both modes.
+/// [`RuleMode::Bytes`] is the default, matching hashcat/JtR's historical
+/// byte-level semantics; [`RuleMode::Unicode`] is the safe choice for
+/// accented or otherwise non-ASCII wordlists.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum RuleMode {
+    #[default]
+    Bytes,
+    Unicode,
+}
 
-#[derive(Debug, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
+#[derive(Debug, Clone, PartialEq, PartialOrd, Eq, Ord, Hash, Serialize, Deserialize)]
 pub enum Rule {
     /// Passthrough
     /// example: p@assW0rd -> p@assW0rd
@@ -14,7 +36,7 @@ pub enum Rule {
 }
 
 
-#[derive(Debug, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
+#[derive(Debug, Clone, PartialEq, PartialOrd, Eq, Ord, Hash, Serialize, Deserialize)]
 pub enum TransformRule {
     // John the Ripper/passwords pro rules
     /// Lowercase the entire input string
@@ -81,23 +103,139 @@ pub enum TransformRule {
     ReplaceWithPrev(usize),
     DuplicateFirstBlock(usize),
     DuplicateLastBlock(usize),
-    // TODO: titlecase rules
+
+    /// Split the input on word boundaries and capitalize only the first
+    /// letter of each segment, rejoining with no separator.
+    /// example: p@ss_word-test -> P@ssWordTest
+    TitleCase,
+    /// Like [`TransformRule::TitleCase`], but alternates between
+    /// capitalizing-first and fully-uppercasing each successive segment.
+    /// example: p@ss_word-test -> P@ssWORDTest
+    AlternatingWordCase,
+
+    /// Replace every match of a regex pattern with a replacement, which may
+    /// reference capture groups as `$1`, `$2`, etc.
+    /// example(PATTERN=(\d+)$, REPLACEMENT=<$1>): p@ssw0rd123 -> p@ssw0rd<123>
+    RegexReplace(String, String),
+
+    /// Split the input on word boundaries and re-join the segments in the given case style.
+    /// example(Pascal): password_reset -> PasswordReset
+    /// example(Camel): password_reset -> passwordReset
+    /// example(Snake): passwordReset -> password_reset
+    ToCase(Case),
+
+    /// Fans out to every case permutation over the first `limit` alphabetic
+    /// positions, independently toggling each one upper/lower relative to
+    /// its original case (`2^min(limit, alphabetic_count, 31)` variants). Only
+    /// [`TransformRule::run_many`] expands this; [`TransformRule::run`]'s
+    /// "first variant" is the input unchanged.
+    /// example(limit=2): ab -> [ab, Ab, aB, AB]
+    CasePermute(usize),
+    /// Fans out to the cartesian product of every substitution in `table`:
+    /// each occurrence of a `from` character may independently stay as-is or
+    /// become any of its `to` alternatives. Only [`TransformRule::run_many`]
+    /// expands this; [`TransformRule::run`]'s "first variant" is the input
+    /// unchanged.
+    /// example(table=[('a', ['@', '4'])]): password -> [password, p@ssword, p4ssword]
+    LeetReplace(Vec<(char, Vec<char>)>),
+}
+
+/// A word-boundary-aware case style, used by [`TransformRule::ToCase`].
+#[derive(Debug, Copy, Clone, PartialEq, PartialOrd, Eq, Ord, Hash, Serialize, Deserialize)]
+pub enum Case {
+    /// Capitalize each segment, joined by a space: `Password Reset`
+    Title,
+    /// Lowercase the first segment, capitalize the rest, no separator: `passwordReset`
+    Camel,
+    /// Capitalize every segment, no separator: `PasswordReset`
+    Pascal,
+    /// Lowercase every segment, joined by `_`: `password_reset`
+    Snake,
+    /// Lowercase every segment, joined by `-`: `password-reset`
+    Kebab,
+    /// Uppercase every segment, joined by `_`: `PASSWORD_RESET`
+    ScreamingSnake,
+}
+
+/// Splits `input` into word segments on explicit delimiters (`_`, `-`, space),
+/// lower/digit -> upper transitions, upper-run -> lower transitions (acronym
+/// boundary sits before the last uppercase letter), and letter <-> digit transitions.
+fn word_boundary_segments(input: &str) -> Vec<String> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut segments: Vec<String> = Vec::new();
+    let mut current: Vec<char> = Vec::new();
+
+    for (i, &c) in chars.iter().enumerate() {
+        if c == '_' || c == '-' || c == ' ' {
+            if !current.is_empty() {
+                segments.push(current.drain(..).collect());
+            }
+            continue;
+        }
+
+        if let Some(&prev) = chars.get(i.wrapping_sub(1)).filter(|_| i > 0) {
+            let lower_or_digit_to_upper = (prev.is_lowercase() || prev.is_ascii_digit()) && c.is_uppercase();
+            let upper_run_to_lower = prev.is_uppercase() && c.is_lowercase() && current.len() > 1;
+            let letter_digit_transition =
+                (prev.is_alphabetic() && c.is_ascii_digit()) || (prev.is_ascii_digit() && c.is_alphabetic());
+
+            if upper_run_to_lower {
+                // The last uppercase letter in `current` starts the new word (acronym boundary).
+                let last = current.pop().unwrap();
+                segments.push(current.drain(..).collect());
+                current.push(last);
+            } else if (lower_or_digit_to_upper || letter_digit_transition) && !current.is_empty() {
+                segments.push(current.drain(..).collect());
+            }
+        }
+
+        current.push(c);
+    }
+    if !current.is_empty() {
+        segments.push(current.into_iter().collect());
+    }
+    segments
 }
 
-#[derive(Debug, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
+fn capitalize_segment(segment: &str) -> String {
+    let mut chars = segment.chars();
+    match chars.next() {
+        None => String::new(),
+        Some(first) => first.to_uppercase().collect::<String>() + chars.as_str().to_lowercase().as_str(),
+    }
+}
+
+impl Case {
+    fn apply(&self, segments: &[String]) -> String {
+        match self {
+            Case::Title => segments.iter().map(|s| capitalize_segment(s)).join(" "),
+            Case::Pascal => segments.iter().map(|s| capitalize_segment(s)).join(""),
+            Case::Camel => segments
+                .iter()
+                .enumerate()
+                .map(|(i, s)| if i == 0 { s.to_lowercase() } else { capitalize_segment(s) })
+                .join(""),
+            Case::Snake => segments.iter().map(|s| s.to_lowercase()).join("_"),
+            Case::Kebab => segments.iter().map(|s| s.to_lowercase()).join("-"),
+            Case::ScreamingSnake => segments.iter().map(|s| s.to_uppercase()).join("_"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, PartialOrd, Eq, Ord, Hash, Serialize, Deserialize)]
 pub enum Truncate {
     Left,
     Right,
     To(usize)
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
+#[derive(Debug, Copy, Clone, PartialEq, PartialOrd, Eq, Ord, Hash, Serialize, Deserialize)]
 pub enum Rotation {
     Left,
     Right,
 }
 
-#[derive(Debug, Clone,  PartialEq, PartialOrd, Eq, Ord, Hash)]
+#[derive(Debug, Clone,  PartialEq, PartialOrd, Eq, Ord, Hash, Serialize, Deserialize)]
 pub enum RejectRule {
     ShorterThan(usize),
     LongerThan(usize),
@@ -108,6 +246,37 @@ pub enum RejectRule {
     NotEndsWith(String),
     NotEqualAt(usize, String),
     ContainsLessThan(usize, String),
+    /// Reject if the input matches the given regex pattern.
+    RegexMatch(String),
+    /// Reject if the input does not match the given regex pattern.
+    RegexNotMatch(String),
+    /// Reject unless the input contains at least one character of the given class.
+    RequiresClass(CharClass),
+    /// Reject if the input contains any character outside the given classes.
+    AllowedOnly(Vec<CharClass>),
+}
+
+/// A coarse character classification used by password-policy reject rules.
+#[derive(Debug, Copy, Clone, PartialEq, PartialOrd, Eq, Ord, Hash, Serialize, Deserialize)]
+pub enum CharClass {
+    Lower,
+    Upper,
+    Digit,
+    Special,
+    Unicode,
+}
+
+impl CharClass {
+    /// Returns whether `c` belongs to this class.
+    pub fn matches(&self, c: char) -> bool {
+        match self {
+            CharClass::Lower => c.is_ascii_lowercase(),
+            CharClass::Upper => c.is_ascii_uppercase(),
+            CharClass::Digit => c.is_ascii_digit(),
+            CharClass::Special => c.is_ascii() && !c.is_ascii_alphanumeric(),
+            CharClass::Unicode => !c.is_ascii(),
+        }
+    }
 }
 
 
@@ -116,27 +285,142 @@ impl Rule {
         basic_simplify(rules)
     }
     pub fn run(&self, input: String) -> Option<String> {
+        self.run_mode(input, RuleMode::Bytes)
+    }
+
+    /// Like [`Rule::run`], but lets the caller select a [`RuleMode`] for
+    /// byte-oriented transforms and length-based reject rules.
+    pub fn run_mode(&self, input: String, mode: RuleMode) -> Option<String> {
         match self {
             Rule::NoOp => Some(input),
-            Rule::Transform(rule) => Some(rule.run(input)),
-            // TODO: add run
-            Rule::Reject(rule) => rule.run(input),
+            Rule::Transform(rule) => Some(rule.run_mode(input, mode)),
+            Rule::Reject(rule) => rule.run_mode(input, mode),
             Rule::End => Some(input),
         }
     }
-    pub fn run_all<'a>(rules: impl  IntoIterator<Item = &'a Rule>, input: String) -> Option<String> {
-        let mut output = Some(input);
+
+    /// Borrow-preserving variant of [`Rule::run`]. `NoOp`/`End` and passing
+    /// reject rules return `input` unchanged with no allocation; only a
+    /// `Transform` rule that actually rewrites the buffer forces an owned copy.
+    pub fn run_cow<'a>(&self, input: Cow<'a, str>) -> Option<Cow<'a, str>> {
+        self.run_cow_mode(input, RuleMode::Bytes)
+    }
+
+    /// Like [`Rule::run_cow`], but lets the caller select a [`RuleMode`].
+    pub fn run_cow_mode<'a>(&self, input: Cow<'a, str>, mode: RuleMode) -> Option<Cow<'a, str>> {
+        match self {
+            Rule::NoOp | Rule::End => Some(input),
+            Rule::Transform(rule) => {
+                let output = rule.run_mode(input.to_string(), mode);
+                if output == *input {
+                    Some(input)
+                } else {
+                    Some(Cow::Owned(output))
+                }
+            }
+            Rule::Reject(rule) => {
+                if rule.check_mode(&input, mode) {
+                    Some(input)
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    /// Applies `rules` to `input` in order, borrowing for as long as possible:
+    /// the result stays borrowed unless a transform rule changes the word.
+    /// Every transform still builds a temporary `String` to compare against,
+    /// even when the word comes out unchanged. Length, substring and
+    /// character-class reject rules allocate nothing, so a chain of them
+    /// (e.g. password-policy filtering) returns `input` without copying it.
+    pub fn run_all<'a>(rules: impl IntoIterator<Item = &'a Rule>, input: &'a str) -> Option<Cow<'a, str>> {
+        Rule::run_all_mode(rules, input, RuleMode::Bytes)
+    }
+
+    /// Like [`Rule::run_all`], but lets the caller select a [`RuleMode`].
+    pub fn run_all_mode<'a>(
+        rules: impl IntoIterator<Item = &'a Rule>,
+        input: &'a str,
+        mode: RuleMode,
+    ) -> Option<Cow<'a, str>> {
+        let mut output = Cow::Borrowed(input);
         for rule in rules {
-            output = rule.run(output.unwrap());
-            if output.is_none() {
-                return None;
+            output = rule.run_cow_mode(output, mode)?;
+        }
+        Some(output)
+    }
+
+    /// Fan-out counterpart to [`Rule::run`]: a [`TransformRule::CasePermute`]
+    /// or [`TransformRule::LeetReplace`] expands `input` into every
+    /// substitution variant instead of just the first one; every other rule
+    /// still returns at most one candidate.
+    pub fn run_many(&self, input: String) -> Vec<String> {
+        self.run_many_mode(input, RuleMode::Bytes)
+    }
+
+    /// Like [`Rule::run_many`], but lets the caller select a [`RuleMode`].
+    pub fn run_many_mode(&self, input: String, mode: RuleMode) -> Vec<String> {
+        match self {
+            Rule::NoOp | Rule::End => vec![input],
+            Rule::Transform(rule) => rule.run_many(input, mode),
+            Rule::Reject(rule) => {
+                if rule.check_mode(&input, mode) {
+                    vec![input]
+                } else {
+                    Vec::new()
+                }
             }
         }
-        output
+    }
+
+    /// Fan-out counterpart to [`Rule::run_all`]: applies `rules` to `input`
+    /// in order, chaining each stage's output candidates into the next
+    /// stage's cartesian product (deduplicated between stages so a run of
+    /// expanding rules doesn't blow up with repeats). Stops early, returning
+    /// an empty `Vec`, as soon as a reject rule eliminates every remaining
+    /// candidate.
+    pub fn run_all_many<'a>(rules: impl IntoIterator<Item = &'a Rule>, input: &str) -> Vec<String> {
+        Rule::run_all_many_mode(rules, input, RuleMode::Bytes)
+    }
+
+    /// Like [`Rule::run_all_many`], but lets the caller select a [`RuleMode`].
+    pub fn run_all_many_mode<'a>(
+        rules: impl IntoIterator<Item = &'a Rule>,
+        input: &str,
+        mode: RuleMode,
+    ) -> Vec<String> {
+        let mut candidates = vec![input.to_string()];
+        for rule in rules {
+            if candidates.is_empty() {
+                break;
+            }
+            candidates = dedup_stable(
+                candidates.into_iter().flat_map(|candidate| rule.run_many_mode(candidate, mode)).collect(),
+            );
+        }
+        candidates
     }
 }
+
+/// Removes duplicate candidates from a fan-out stage, keeping the first
+/// occurrence of each - used by [`Rule::run_all_many`] to bound the
+/// combinatorial blow-up of chained expanding rules.
+fn dedup_stable(words: Vec<String>) -> Vec<String> {
+    let mut seen = std::collections::HashSet::new();
+    words.into_iter().filter(|word| seen.insert(word.clone())).collect()
+}
+
 impl TransformRule {
     pub fn run(&self, input: String) -> String {
+        self.run_mode(input, RuleMode::Bytes)
+    }
+
+    /// Like [`TransformRule::run`], but lets the caller select a [`RuleMode`]
+    /// so [`TransformRule::BitwiseShiftLeft`]/`Right` and
+    /// [`TransformRule::AsciiIncrement`]/`Decrement` operate on raw bytes or
+    /// on whole codepoints instead of truncating a `char` with `as u8`.
+    pub fn run_mode(&self, input: String, mode: RuleMode) -> String {
         match self {
             TransformRule::Lowercase => input.to_lowercase(),
             TransformRule::Uppercase => input.to_uppercase(),
@@ -257,32 +541,16 @@ impl TransformRule {
                 chars.rev().collect::<String>() + &first.to_string() + &second.to_string()
             },
             TransformRule::BitwiseShiftLeft(n) => {
-                input.chars().map(|c| {
-                    let mut c = c as u8;
-                    c = c << *n;
-                    c as char
-                }).collect()
+                Units::from_str(&input, mode).map_units(|u| u.wrapping_shl(*n as u32)).into_string()
             },
             TransformRule::BitwiseShiftRight(n) => {
-                input.chars().map(|c| {
-                    let mut c = c as u8;
-                    c = c >> *n;
-                    c as char
-                }).collect()
+                Units::from_str(&input, mode).map_units(|u| u.wrapping_shr(*n as u32)).into_string()
             },
             TransformRule::AsciiIncrement(n) => {
-                input.chars().map(|c| {
-                    let mut c = c as u8;
-                    c = c.wrapping_add(*n as u8);
-                    c as char
-                }).collect()
+                Units::from_str(&input, mode).map_units(|u| u.wrapping_add(*n as u32)).into_string()
             },
             TransformRule::AsciiDecrement(n) => {
-                input.chars().map(|c| {
-                    let mut c = c as u8;
-                    c = c.wrapping_sub(*n as u8);
-                    c as char
-                }).collect()
+                Units::from_str(&input, mode).map_units(|u| u.wrapping_sub(*n as u32)).into_string()
             },
             TransformRule::ReplaceWithNext(n) => {
                 input.chars().enumerate().map(|(i, c)| {
@@ -309,76 +577,158 @@ impl TransformRule {
                 let last = input.chars().rev().take(*n).collect::<Vec<char>>().iter().rev().collect::<String>();
                 input + &last
             },
-        }
-    }
-}
-
-impl RejectRule {
-    pub fn run(&self, input: String) -> Option<String> {
-        match self {
-            RejectRule::ShorterThan(n) => {
-                if input.len() >= *n {
-                    Some(input)
-                } else {
-                    None
-                }
-            },
-            RejectRule::LongerThan(n) => {
-                if input.len() <= *n {
-                    Some(input)
-                } else {
-                    None
-                }
+            TransformRule::TitleCase => {
+                word_boundary_segments(&input).iter().map(|s| capitalize_segment(s)).join("")
             },
-            RejectRule::NotEqualTo(n) => {
-                if input.len() == *n {
-                    Some(input)
-                } else {
-                    None
-                }
+            TransformRule::AlternatingWordCase => {
+                word_boundary_segments(&input)
+                    .iter()
+                    .enumerate()
+                    .map(|(i, s)| if i % 2 == 0 { capitalize_segment(s) } else { s.to_uppercase() })
+                    .join("")
             },
-            RejectRule::Contains(s) => {
-                if !input.contains(s) {
-                    Some(input)
-                } else {
-                    None
+            TransformRule::RegexReplace(pattern, replacement) => {
+                match Regex::new(pattern) {
+                    Ok(re) => re.replace_all(&input, replacement.as_str()).into_owned(),
+                    Err(_) => input,
                 }
             },
-            RejectRule::NotContains(s) => {
-                if input.contains(s) {
-                    Some(input)
-                } else {
-                    None
-                }
+            TransformRule::ToCase(case) => case.apply(&word_boundary_segments(&input)),
+            TransformRule::CasePermute(limit) => {
+                case_permute(&input, *limit).into_iter().next().unwrap_or(input)
             },
-            RejectRule::NotStartsWith(s) => {
-                if input.starts_with(s) {
-                    Some(input)
-                } else {
-                    None
-                }
+            TransformRule::LeetReplace(table) => {
+                leet_replace(&input, table).into_iter().next().unwrap_or(input)
             },
-            RejectRule::NotEndsWith(s) => {
-                if input.ends_with(s) {
-                    Some(input)
-                } else {
-                    None
+        }
+    }
+
+    /// Fan-out counterpart to [`TransformRule::run_mode`]. Every rule but
+    /// [`TransformRule::CasePermute`]/[`TransformRule::LeetReplace`] still
+    /// produces exactly one output; those two expand into every
+    /// substitution variant instead.
+    pub fn run_many(&self, input: String, mode: RuleMode) -> Vec<String> {
+        match self {
+            TransformRule::CasePermute(limit) => case_permute(&input, *limit),
+            TransformRule::LeetReplace(table) => leet_replace(&input, table),
+            other => vec![other.run_mode(input, mode)],
+        }
+    }
+}
+
+/// Toggles every alphabetic position up to `limit` between its original
+/// case and the opposite, producing every combination - the expansion
+/// behind [`TransformRule::CasePermute`].
+fn case_permute(input: &str, limit: usize) -> Vec<String> {
+    let chars: Vec<char> = input.chars().collect();
+    let positions: Vec<usize> = chars
+        .iter()
+        .enumerate()
+        .filter(|(_, c)| c.is_alphabetic())
+        .map(|(i, _)| i)
+        .take(limit.min(31))
+        .collect();
+
+    (0u32..(1u32 << positions.len()))
+        .map(|mask| {
+            let mut variant = chars.clone();
+            for (bit, &pos) in positions.iter().enumerate() {
+                if mask & (1 << bit) != 0 {
+                    variant[pos] = toggle_case(variant[pos]);
                 }
-            },
+            }
+            variant.into_iter().collect()
+        })
+        .collect()
+}
+
+fn toggle_case(c: char) -> char {
+    if c.is_uppercase() {
+        c.to_lowercase().next().unwrap()
+    } else {
+        c.to_uppercase().next().unwrap()
+    }
+}
+
+/// Expands every occurrence of a `from` character in `table` into the
+/// cartesian product of staying as-is or becoming any of its `to`
+/// alternatives - the expansion behind [`TransformRule::LeetReplace`].
+fn leet_replace(input: &str, table: &[(char, Vec<char>)]) -> Vec<String> {
+    let mut variants: Vec<String> = vec![String::new()];
+    for c in input.chars() {
+        let options: Vec<char> = match table.iter().find(|(from, _)| *from == c) {
+            Some((_, substitutes)) => std::iter::once(c).chain(substitutes.iter().copied()).collect(),
+            None => vec![c],
+        };
+
+        variants = variants
+            .into_iter()
+            .flat_map(|prefix| {
+                options.iter().map(move |&opt| {
+                    let mut variant = prefix.clone();
+                    variant.push(opt);
+                    variant
+                })
+            })
+            .collect();
+    }
+    variants
+}
+
+impl RejectRule {
+    /// Pure predicate form of [`RejectRule::run`]: returns whether `input`
+    /// passes this rule, without taking ownership of it.
+    pub fn check(&self, input: &str) -> bool {
+        self.check_mode(input, RuleMode::Bytes)
+    }
+
+    /// Like [`RejectRule::check`], but lets the caller select a [`RuleMode`]
+    /// so [`RejectRule::ShorterThan`]/`LongerThan`/`NotEqualTo` measure
+    /// length in raw bytes or in codepoints.
+    pub fn check_mode(&self, input: &str, mode: RuleMode) -> bool {
+        let len = || match mode {
+            RuleMode::Bytes => input.len(),
+            RuleMode::Unicode => input.chars().count(),
+        };
+        match self {
+            RejectRule::ShorterThan(n) => len() >= *n,
+            RejectRule::LongerThan(n) => len() <= *n,
+            RejectRule::NotEqualTo(n) => len() == *n,
+            RejectRule::Contains(s) => !input.contains(s),
+            RejectRule::NotContains(s) => input.contains(s),
+            RejectRule::NotStartsWith(s) => input.starts_with(s),
+            RejectRule::NotEndsWith(s) => input.ends_with(s),
             RejectRule::NotEqualAt(n, s) => {
-                if input.chars().skip(*n).take(s.len()).collect::<String>() == *s {
-                    Some(input)
-                } else {
-                    None
+                input.chars().skip(*n).take(s.chars().count()).collect::<String>() == *s
+            },
+            RejectRule::ContainsLessThan(n, s) => input.matches(s).count() >= *n,
+            RejectRule::RegexMatch(pattern) => {
+                match Regex::new(pattern) {
+                    Ok(re) => !re.is_match(input),
+                    Err(_) => true,
                 }
             },
-            RejectRule::ContainsLessThan(n, s) => {
-                if input.matches(s).count() >= *n {
-                    Some(input)
-                } else {
-                    None
+            RejectRule::RegexNotMatch(pattern) => {
+                match Regex::new(pattern) {
+                    Ok(re) => re.is_match(input),
+                    Err(_) => false,
                 }
             },
+            RejectRule::RequiresClass(class) => input.chars().any(|c| class.matches(c)),
+            RejectRule::AllowedOnly(classes) => input.chars().all(|c| classes.iter().any(|class| class.matches(c))),
+        }
+    }
+
+    pub fn run(&self, input: String) -> Option<String> {
+        self.run_mode(input, RuleMode::Bytes)
+    }
+
+    /// Like [`RejectRule::run`], but lets the caller select a [`RuleMode`].
+    pub fn run_mode(&self, input: String, mode: RuleMode) -> Option<String> {
+        if self.check_mode(&input, mode) {
+            Some(input)
+        } else {
+            None
         }
     }
 }
@@ -386,6 +736,10 @@ impl RejectRule {
 
 
 fn basic_simplify(rules: Vec<Rule>) -> Vec<Rule> {
+    simplify_length_rejects(coalesce_adjacent_rules(rules))
+}
+
+fn coalesce_adjacent_rules(rules: Vec<Rule>) -> Vec<Rule> {
     rules
         .into_iter()
         .filter(|rule| match rule {
@@ -441,3 +795,73 @@ fn basic_simplify(rules: Vec<Rule>) -> Vec<Rule> {
         })
         .collect()
 }
+
+/// Walks `rules` tracking the feasible length interval `[lo, hi]` implied by
+/// any run of adjacent `ShorterThan`/`LongerThan`/`NotEqualTo` reject rules,
+/// folding each run into the minimal set of reject rules that reproduce it.
+/// Any other rule flushes the interval first - a length-changing transform
+/// (`Append`, `Truncate`, `Duplicate`, ...) genuinely invalidates it, and
+/// nothing else is assumed reorderable past it, so this only merges runs
+/// that are already adjacent rather than chasing every equivalent reordering.
+/// If a run's interval becomes empty (`lo > hi`), the whole chain can never
+/// pass regardless of what follows, so the entire rule list collapses to one
+/// canonical reject-all rule.
+fn simplify_length_rejects(rules: Vec<Rule>) -> Vec<Rule> {
+    let mut output = Vec::with_capacity(rules.len());
+    let mut lo: usize = 0;
+    let mut hi: usize = usize::MAX;
+    let mut has_constraint = false;
+
+    for rule in rules {
+        match &rule {
+            Rule::Reject(RejectRule::ShorterThan(n)) => {
+                lo = lo.max(*n);
+                has_constraint = true;
+            }
+            Rule::Reject(RejectRule::LongerThan(n)) => {
+                hi = hi.min(*n);
+                has_constraint = true;
+            }
+            Rule::Reject(RejectRule::NotEqualTo(n)) => {
+                lo = lo.max(*n);
+                hi = hi.min(*n);
+                has_constraint = true;
+            }
+            _ => {
+                flush_length_interval(&mut output, lo, hi, has_constraint);
+                lo = 0;
+                hi = usize::MAX;
+                has_constraint = false;
+                output.push(rule);
+                continue;
+            }
+        }
+
+        if has_constraint && lo > hi {
+            return vec![Rule::Reject(RejectRule::ShorterThan(usize::MAX))];
+        }
+    }
+
+    flush_length_interval(&mut output, lo, hi, has_constraint);
+    output
+}
+
+/// Appends the minimal set of reject rules reproducing `[lo, hi]` to
+/// `output`. A single-point interval (`lo == hi`) becomes one `NotEqualTo`;
+/// a bound at its vacuous default (`lo == 0` or `hi == usize::MAX`) is
+/// dropped rather than emitted as a no-op reject rule.
+fn flush_length_interval(output: &mut Vec<Rule>, lo: usize, hi: usize, has_constraint: bool) {
+    if !has_constraint {
+        return;
+    }
+    if lo == hi {
+        output.push(Rule::Reject(RejectRule::NotEqualTo(lo)));
+        return;
+    }
+    if lo > 0 {
+        output.push(Rule::Reject(RejectRule::ShorterThan(lo)));
+    }
+    if hi < usize::MAX {
+        output.push(Rule::Reject(RejectRule::LongerThan(hi)));
+    }
+}
@@ -4,8 +4,14 @@
 //! cracking attacks. Hashcat rules consist of a series of commands that define transformations
 //! to be applied to password candidates.
 //!
-//! The parser uses the `winnow` crate to efficiently parse rule syntax into structured data
-//! that can be used by the transformation engine to apply the rules to input words.
+//! The parser uses the `winnow` crate to drive the overall line-by-line scan
+//! (tracking byte offsets for [`ParseError`] and handling [`ParseMode::Verbose`]
+//! whitespace/comments), while the individual operators themselves are
+//! recognized by the declarative `rust-peg` grammar in [`crate::grammar`].
+//!
+//! [`rules_to_string`] is the reverse direction: rendering a `Vec<Rule>` back
+//! into the textual rule-language [`parse_line`] accepts, for writing a
+//! simplified/programmatically-built rule chain back out to a `.rule` file.
 //!
 //! ## Rule Format
 //!
@@ -16,12 +22,15 @@
 //! - `^A` prepends character 'A'
 //! - `c` capitalizes the first letter
 //!
+use std::sync::Mutex;
+
 use winnow::prelude::*;
 use winnow::Parser;
-use winnow::ascii::dec_uint;
-use winnow::token::{one_of,any,literal,rest};
-use winnow::combinator::{dispatch,empty,fail,alt,separated,repeat,terminated,eof};
+use winnow::ascii::{dec_uint,space0};
+use winnow::token::{one_of,any};
+use winnow::combinator::{dispatch,fail,alt,separated,repeat};
 
+use crate::grammar;
 use crate::lang::*;
 
 /// Parses a range in the format `N:M` where N and M are decimal integers separated by a colon.
@@ -94,10 +103,147 @@ pub fn range_parser(input: &mut &str) -> ModalResult<(usize, usize)> {
     alt((range_parser_delim, range_parse_raw, fail)).parse_next(input)
 }
 
+/// Parses a `delim`-terminated span, honoring `\` as an escape for the
+/// delimiter itself (or a literal `\`) within the span. Any other backslash
+/// sequence (e.g. a regex escape like `\d`) is passed through verbatim -
+/// `delimited_field` only needs to know where the field ends, not how to
+/// interpret the regex syntax living inside it.
+///
+/// Used by the `~s/PATTERN/REPLACEMENT/`, `~m/PATTERN/` and `~M/PATTERN/`
+/// regex rules to pull out delimiter-escaped pattern/replacement text.
+///
+/// # Examples
+///
+/// ```
+/// # use crate::_core::parser::delimited_field;
+/// let mut input = "foo/bar";
+/// assert_eq!(delimited_field('/')(&mut input), Ok("foo".to_string()));
+/// assert_eq!(input, "bar");
+/// ```
+///
+/// A backslash that isn't escaping the delimiter (or another backslash)
+/// passes straight through, so regex metacharacters like `\d` survive intact:
+///
+/// ```
+/// # use crate::_core::parser::delimited_field;
+/// let mut input = r"(\d+)/rest";
+/// assert_eq!(delimited_field('/')(&mut input), Ok(r"(\d+)".to_string()));
+/// ```
+pub fn delimited_field<'s>(delim: char) -> impl FnMut(&mut &'s str) -> ModalResult<String> {
+    move |input: &mut &'s str| {
+        let mut out = String::new();
+        loop {
+            match input.chars().next() {
+                None => return fail::<_, String, _>.parse_next(input),
+                Some(c) if c == delim => {
+                    *input = &input[c.len_utf8()..];
+                    return Ok(out);
+                }
+                Some('\\') => match input[1..].chars().next() {
+                    Some(next) if next == delim || next == '\\' => {
+                        out.push(next);
+                        *input = &input[1 + next.len_utf8()..];
+                    }
+                    _ => {
+                        out.push('\\');
+                        *input = &input[1..];
+                    }
+                },
+                Some(c) => {
+                    out.push(c);
+                    *input = &input[c.len_utf8()..];
+                }
+            }
+        }
+    }
+}
+
+/// A downstream-registered operator production: given the remaining input,
+/// returns the `Rule` it recognized and how many bytes it consumed, or `None`
+/// to decline (letting the next extension, or the final parse failure, take
+/// over). Registered via [`register_operator_extension`].
+pub type OperatorExtension = fn(&str) -> Option<(Rule, usize)>;
+
+static OPERATOR_EXTENSIONS: Mutex<Vec<OperatorExtension>> = Mutex::new(Vec::new());
+
+/// Registers an extra operator production to be tried whenever a rule line
+/// doesn't match any built-in opcode (see [`grammar::rules::op`]), so
+/// downstream crates can define their own mangling rules - e.g. a
+/// crate-specific leetspeak substitution - without forking this parser.
+/// Extensions are tried in registration order after the built-in grammar.
+pub fn register_operator_extension(extension: OperatorExtension) {
+    OPERATOR_EXTENSIONS.lock().unwrap().push(extension);
+}
+
+fn try_operator_extensions(input: &str) -> Option<(Rule, usize)> {
+    OPERATOR_EXTENSIONS.lock().unwrap().iter().find_map(|extension| extension(input))
+}
+
+/// A single `#`, which consumes the rest of the line as a no-op comment.
+fn comment_rule(input: &mut &str) -> ModalResult<Rule> {
+    if input.starts_with('#') {
+        let end = input.find('\n').unwrap_or(input.len());
+        *input = &input[end..];
+        Ok(Rule::NoOp)
+    } else {
+        fail::<_, Rule, _>.parse_next(input)
+    }
+}
+
+/// Every built-in opcode except the regex rules, expressed declaratively in
+/// [`grammar::rules::op`] - see that module for the grammar itself.
+fn builtin_op(input: &mut &str) -> ModalResult<Rule> {
+    match grammar::rules::op(input) {
+        Ok((rule, consumed)) => {
+            *input = &input[consumed..];
+            Ok(rule)
+        }
+        Err(_) => fail::<_, Rule, _>.parse_next(input),
+    }
+}
+
+/// `~s<D>PATTERN<D>REPLACEMENT<D>`, `~m<D>PATTERN<D>`, `~M<D>PATTERN<D>`.
+///
+/// These need escape-aware delimiter scanning ([`delimited_field`]), which
+/// isn't expressible as a plain peg literal/character-class production, so
+/// they stay hand-written here rather than in [`grammar`].
+fn regex_op(input: &mut &str) -> ModalResult<Rule> {
+    dispatch! { any;
+        '~' => dispatch! { any;
+            's' => (any.verify(|c: &char| !c.is_whitespace()))
+                .flat_map(|delim: char| (delimited_field(delim), delimited_field(delim)))
+                .map(|(pattern, replacement)| Rule::Transform(TransformRule::RegexReplace(pattern, replacement))),
+            'm' => (any.verify(|c: &char| !c.is_whitespace()))
+                .flat_map(|delim: char| delimited_field(delim))
+                .map(|pattern| Rule::Reject(RejectRule::RegexMatch(pattern))),
+            'M' => (any.verify(|c: &char| !c.is_whitespace()))
+                .flat_map(|delim: char| delimited_field(delim))
+                .map(|pattern| Rule::Reject(RejectRule::RegexNotMatch(pattern))),
+            _ => fail::<_, Rule, _>,
+        },
+        _ => fail::<_, Rule, _>,
+    }.parse_next(input)
+}
+
+fn extension_op(input: &mut &str) -> ModalResult<Rule> {
+    match try_operator_extensions(input) {
+        Some((rule, consumed)) => {
+            *input = &input[consumed..];
+            Ok(rule)
+        }
+        None => fail::<_, Rule, _>.parse_next(input),
+    }
+}
+
 /// Parses a single Hashcat rule command from the input string.
 ///
-/// This function recognizes all standard Hashcat rule commands and maps them to the appropriate 
-/// `Rule` enum variant. It consumes the command character and any parameters from the input string.
+/// This function recognizes all standard Hashcat rule commands and maps them
+/// to the appropriate `Rule` enum variant, consuming the command character
+/// and any parameters from the input string. Most opcodes are recognized by
+/// the declarative grammar in [`grammar::rules::op`]; the regex rules
+/// (`~s`, `~m`, `~M`) are parsed here by hand because their escape-aware
+/// delimited fields aren't expressible as plain peg literals, and any
+/// [`OperatorExtension`]s registered by downstream crates are tried last.
 ///
 /// # Supported Commands
 ///
@@ -153,6 +299,32 @@ pub fn range_parser(input: &mut &str) -> ModalResult<(usize, usize)> {
 /// - `=[N][C]` - Reject if character at position N is not C
 /// - `%[N][C]` - Reject if word contains fewer than N instances of character C
 ///
+/// ## Regex Rules:
+/// - `~s<D>PATTERN<D>REPLACEMENT<D>` - Replace every regex match with REPLACEMENT (supports `$1`-style captures)
+/// - `~m<D>PATTERN<D>` - Reject if word matches the regex
+/// - `~M<D>PATTERN<D>` - Reject if word does not match the regex
+///
+/// `<D>` is any non-whitespace delimiter character chosen by the caller (e.g. `/`);
+/// it may appear literally inside PATTERN/REPLACEMENT by escaping it as `\<D>`.
+///
+/// ## Case Rules:
+/// - `~cT` - Title Case (segments joined by a space)
+/// - `~cC` - camelCase
+/// - `~cP` - PascalCase
+/// - `~cS` - snake_case
+/// - `~cK` - kebab-case
+/// - `~cU` - SCREAMING_SNAKE_CASE
+///
+/// ## Fan-out Rules
+///
+/// Only expanded by [`crate::lang::TransformRule::run_many`]/[`crate::lang::Rule::run_all_many`];
+/// [`parse_rule`] itself just produces the `Rule`, same as any other operator.
+///
+/// - `~p[N]` - Case-permute the first N alphabetic positions
+/// - `~l<FROM><N><TO...>` - Leet-replace FROM with any of the N (a single digit, 0-9)
+///   following TO characters; repeatable for multiple substitutions (e.g. `~la2@4e13`
+///   substitutes `a` with `@`/`4` and `e` with `3`)
+///
 /// # Returns
 ///
 /// A `ModalResult<Rule>` containing either the parsed rule or an error.
@@ -163,7 +335,7 @@ pub fn range_parser(input: &mut &str) -> ModalResult<(usize, usize)> {
 /// ```
 /// # use crate::_core::parser::parse_rule;
 /// # use crate::_core::lang::{Rule, TransformRule};
-/// 
+///
 /// let mut input = "l";
 /// assert_eq!(parse_rule(&mut input), Ok(Rule::Transform(TransformRule::Lowercase)));
 /// ```
@@ -172,16 +344,16 @@ pub fn range_parser(input: &mut &str) -> ModalResult<(usize, usize)> {
 /// ```
 /// # use crate::_core::parser::parse_rule;
 /// # use crate::_core::lang::{Rule, TransformRule};
-/// 
+///
 /// let mut input = "$1";
 /// assert_eq!(parse_rule(&mut input), Ok(Rule::Transform(TransformRule::Append("1".to_string()))));
 /// ```
-/// 
+///
 /// Parse a rotate rule:
 /// ```
 /// # use crate::_core::parser::parse_rule;
 /// # use crate::_core::lang::{Rule, TransformRule, Rotation};
-/// 
+///
 /// let mut input = "{";
 /// assert_eq!(parse_rule(&mut input), Ok(Rule::Transform(TransformRule::Rotate(Rotation::Left))));
 /// ```
@@ -189,80 +361,100 @@ pub fn range_parser(input: &mut &str) -> ModalResult<(usize, usize)> {
 /// ```
 /// # use crate::_core::parser::parse_rule;
 /// # use crate::_core::lang::{Rule, RejectRule};
-/// 
+///
 /// let mut input = "<8";
 /// assert_eq!(parse_rule(&mut input), Ok(Rule::Reject(RejectRule::LongerThan(8))));
 /// ```
 pub fn parse_rule(input: &mut &str) -> ModalResult<Rule> {
-    dispatch! { any;
-        '#' => rest.value(Rule::NoOp),
-        ':' => empty.value(Rule::NoOp),
-        'l' => empty.value(Rule::Transform(TransformRule::Lowercase)),
-        'u' => empty.value(Rule::Transform(TransformRule::Uppercase)),
-        'c' => empty.value(Rule::Transform(TransformRule::Capitalize)),
-        'C' => empty.value(Rule::Transform(TransformRule::InvertCapitalize)),
-        't' => empty.value(Rule::Transform(TransformRule::ToggleCase(None))),
-        'T' => dec_uint.map(|n| Rule::Transform(TransformRule::ToggleCase(Some(n)))),
-        'r' => empty.value(Rule::Transform(TransformRule::Reverse)),
-        'd' => empty.value(Rule::Transform(TransformRule::Duplicate(None))),
-        'p' => dec_uint.map(|n| Rule::Transform(TransformRule::Duplicate(Some(n)))),
-        'f' => empty.value(Rule::Transform(TransformRule::Reflect)),
-        '{' => empty.value(Rule::Transform(TransformRule::Rotate(Rotation::Left))),
-        '}' => empty.value(Rule::Transform(TransformRule::Rotate(Rotation::Right))),
-        '$' => any.map(|c: char| Rule::Transform(TransformRule::Append(c.to_string()))),
-        '^' => any.map(|c: char| Rule::Transform(TransformRule::Prepend(c.to_string()))),
-        '[' => empty.value(Rule::Transform(TransformRule::Truncate(Truncate::Left))),
-        ']' => empty.value(Rule::Transform(TransformRule::Truncate(Truncate::Right))),
-        'D' => dec_uint.map(|i| Rule::Transform(TransformRule::Delete(i))),
-        'x' => range_parser.map(|r| Rule::Transform(TransformRule::Extract(r.0,r.1))),
-        'O' => range_parser.map(|r| Rule::Transform(TransformRule::Omit(r.0,r.1))),
-        'i' => (any, dec_uint).map(|(c, i): (char, usize)| Rule::Transform(TransformRule::Insert(i, c.to_string()))),
-        'o' => (dec_uint, any).map(|(i, c): (usize, char)| Rule::Transform(TransformRule::Overwrite(i, c.to_string()))),
-        '\'' => dec_uint.map(|n| Rule::Transform(TransformRule::Truncate(Truncate::To(n)))),
-        's' => (any,any).map(|(a,b): (char,char)| Rule::Transform(TransformRule::Replace(a.to_string(), b.to_string()))),
-        '@' => any.map(|c: char| Rule::Transform(TransformRule::Purge(c.to_string()))),
-        'z' => dec_uint.map(|n| Rule::Transform(TransformRule::DuplicateFirst(n))),
-        'Z' => dec_uint.map(|n| Rule::Transform(TransformRule::DuplicateLast(n))),
-        'q' => empty.value(Rule::Transform(TransformRule::DuplicateAll)),
-        // hashcat-specific transformations
-        'k' => empty.value(Rule::Transform(TransformRule::SwapFront)),
-        'K' => empty.value(Rule::Transform(TransformRule::SwapBack)),
-        '*' => range_parser.map(|r| Rule::Transform(TransformRule::Swap(r.0,r.1))),
-        'L' => dec_uint.map(|i| Rule::Transform(TransformRule::BitwiseShiftLeft(i))),
-        'R' => dec_uint.map(|i| Rule::Transform(TransformRule::BitwiseShiftRight(i))),
-        '+' => dec_uint.map(|i| Rule::Transform(TransformRule::AsciiIncrement(i))),
-        '-' => dec_uint.map(|i| Rule::Transform(TransformRule::AsciiDecrement(i))),
-        '.' => dec_uint.map(|i| Rule::Transform(TransformRule::ReplaceWithNext(i))),
-        ',' => dec_uint.map(|i| Rule::Transform(TransformRule::ReplaceWithPrev(i))),
-        'y' => dec_uint.map(|n| Rule::Transform(TransformRule::DuplicateFirstBlock(n))),
-        'Y' => dec_uint.map(|n| Rule::Transform(TransformRule::DuplicateLastBlock(n))),
+    alt((comment_rule, builtin_op, regex_op, extension_op)).parse_next(input)
+}
 
-        // reject rules
-        '<' => dec_uint.map(|n| Rule::Reject(RejectRule::LongerThan(n))),
-        '>' => dec_uint.map(|n| Rule::Reject(RejectRule::ShorterThan(n))),
-        '_' => dec_uint.map(|n| Rule::Reject(RejectRule::NotEqualTo(n))),
-        '!' => any.map(|c: char| Rule::Reject(RejectRule::Contains(c.to_string()))),
-        '/' => any.map(|c: char| Rule::Reject(RejectRule::NotContains(c.to_string()))),
-        '(' => any.map(|c: char| Rule::Reject(RejectRule::NotStartsWith(c.to_string()))),
-        ')' => any.map(|c: char| Rule::Reject(RejectRule::NotEndsWith(c.to_string()))),
-        '=' => (dec_uint, any).map(|(i, c): (usize, char)| Rule::Reject(RejectRule::NotEqualAt(i, c.to_string()))),
-        '%' => (dec_uint, any).map(|(n, c): (usize, char)| Rule::Reject(RejectRule::ContainsLessThan(n, c.to_string()))),
-        _ => fail::<_, Rule, _>,
-    }.parse_next(input)
+/// Selects how [`parse_line_mode`] treats whitespace and comments.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ParseMode {
+    /// Byte-for-byte compatible with Hashcat: no whitespace between commands.
+    /// A `#` still begins a no-op comment that runs to end of line, wherever
+    /// it appears.
+    Strict,
+    /// Horizontal whitespace (spaces/tabs) between commands is ignored, and a
+    /// `#` anywhere begins a comment that runs to end of line.
+    Verbose,
 }
 
-/// Parses a series of Hashcat rules from the input string.
-/// 
-/// Simply calls the `parse_rule` function repeatedly until the input string is exhausted or a newline is reached.
-/// Returns a vector of `Rule` enum variants.
-/// 
+/// The single-character opcodes `parse_rule` recognizes at the start of a rule.
+const RULE_OPCODES: &[&str] = &[
+    ":", "l", "u", "c", "C", "t", "T", "r", "d", "p", "f", "{", "}", "$", "^",
+    "[", "]", "D", "x", "O", "i", "o", "'", "s", "@", "z", "Z", "q", "k", "K",
+    "*", "L", "R", "+", "-", ".", ",", "y", "Y", "<", ">", "_", "!", "/", "(",
+    ")", "=", "%", "~", "#",
+];
+
+/// A structured parse failure with enough context to render a caret pointing
+/// at the offending byte of the original rule line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Byte offset of the failure within the original input.
+    pub byte_offset: usize,
+    /// 1-indexed line number the failure occurred on.
+    pub line: usize,
+    /// 1-indexed character column (within `line`) the failure occurred at.
+    pub col: usize,
+    /// The set of rule opcodes that would have been accepted at this position.
+    pub expected: Vec<&'static str>,
+    /// The character that triggered the failure, or `None` at end of input.
+    pub found: Option<char>,
+    source_line: String,
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Parsing Error at line {}, column {}:", self.line, self.col)?;
+        writeln!(f, "  {}", self.source_line)?;
+        writeln!(f, "  {}^", " ".repeat(self.col.saturating_sub(1)))?;
+        match self.found {
+            Some(c) => write!(f, "unexpected character `{}`; expected one of: {}", c, self.expected.join(", ")),
+            None => write!(f, "unexpected end of input; expected one of: {}", self.expected.join(", ")),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+fn build_parse_error(original: &str, byte_offset: usize) -> ParseError {
+    let mut line = 1;
+    let mut line_start = 0;
+    for (i, c) in original.char_indices() {
+        if i >= byte_offset {
+            break;
+        }
+        if c == '\n' {
+            line += 1;
+            line_start = i + 1;
+        }
+    }
+    let source_line = original[line_start..].lines().next().unwrap_or("").to_string();
+    let col = original[line_start..byte_offset].chars().count() + 1;
+    let found = original[byte_offset..].chars().next();
+
+    ParseError {
+        byte_offset,
+        line,
+        col,
+        expected: RULE_OPCODES.to_vec(),
+        found,
+        source_line,
+    }
+}
+
+/// Parses a series of Hashcat rules from the input string, like
+/// [`parse_line_mode`] with [`ParseMode::Strict`].
+///
 /// # Examples
-/// 
-/// Parse a series of rules:
+///
 /// ```
 /// # use crate::_core::parser::parse_line;
 /// # use crate::_core::lang::{Rule, TransformRule};
-/// 
+///
 /// let mut input = "lu$1";
 /// assert_eq!(parse_line(&mut input), Ok(vec![
 ///     Rule::Transform(TransformRule::Lowercase),
@@ -270,12 +462,12 @@ pub fn parse_rule(input: &mut &str) -> ModalResult<Rule> {
 ///     Rule::Transform(TransformRule::Append("1".to_string()))
 /// ]));
 /// ```
-/// 
+///
 /// Interrupt parsing at a newline:
 /// ```
 /// # use crate::_core::parser::parse_line;
 /// # use crate::_core::lang::{Rule, TransformRule};
-/// 
+///
 /// let mut input = "lu$1\nl";
 /// assert_eq!(parse_line(&mut input), Ok(vec![
 ///    Rule::Transform(TransformRule::Lowercase),
@@ -283,6 +475,175 @@ pub fn parse_rule(input: &mut &str) -> ModalResult<Rule> {
 ///    Rule::Transform(TransformRule::Append("1".to_string()))
 /// ]));
 /// ```
-pub fn parse_line(input: &mut &str) -> ModalResult<Vec<Rule>> {
-    terminated(repeat(0.., parse_rule), alt((literal('\n'), eof))).parse_next(input)
+pub fn parse_line(input: &mut &str) -> Result<Vec<Rule>, ParseError> {
+    parse_line_mode(input, ParseMode::Strict)
+}
+
+/// Parses a series of rules like [`parse_line`], but honoring `mode`.
+///
+/// In [`ParseMode::Verbose`] mode, spaces and tabs between rule tokens are
+/// skipped and `#` starts a comment that runs to the end of the line,
+/// wherever it appears - e.g. `l  $1  # lowercase then append one`.
+///
+/// On failure, returns a [`ParseError`] carrying the byte offset, line/column
+/// and a caret-renderable `Display` impl, rather than swallowing the whole
+/// rule line.
+///
+/// # Examples
+///
+/// ```
+/// # use crate::_core::parser::{parse_line_mode, ParseMode};
+/// # use crate::_core::lang::{Rule, TransformRule};
+///
+/// let mut input = "l  $1  # lowercase then append one";
+/// assert_eq!(parse_line_mode(&mut input, ParseMode::Verbose), Ok(vec![
+///     Rule::Transform(TransformRule::Lowercase),
+///     Rule::Transform(TransformRule::Append("1".to_string())),
+///     Rule::NoOp,
+/// ]));
+/// ```
+pub fn parse_line_mode(input: &mut &str, mode: ParseMode) -> Result<Vec<Rule>, ParseError> {
+    let original: &str = input;
+    let mut rules = Vec::new();
+    loop {
+        if mode == ParseMode::Verbose {
+            let _: ModalResult<&str> = space0.parse_next(input);
+        }
+        if input.is_empty() {
+            return Ok(rules);
+        }
+        if let Some(rest) = input.strip_prefix('\n') {
+            *input = rest;
+            return Ok(rules);
+        }
+
+        let remaining_before = input.len();
+        match parse_rule.parse_next(input) {
+            Ok(rule) => rules.push(rule),
+            Err(_) => {
+                let byte_offset = original.len() - remaining_before;
+                return Err(build_parse_error(original, byte_offset));
+            }
+        }
+    }
+}
+
+/// Renders a full rule line back to text, concatenating each rule's token
+/// with no separator - the inverse of [`parse_line`]/[`parse_line_mode`].
+///
+/// # Examples
+///
+/// ```
+/// # use crate::_core::parser::{parse_line, rules_to_string};
+///
+/// let mut input = "lu$1";
+/// let rules = parse_line(&mut input).unwrap();
+/// assert_eq!(rules_to_string(&rules), "lu$1");
+/// ```
+pub fn rules_to_string(rules: &[Rule]) -> String {
+    rules.iter().map(to_rule_string).collect()
+}
+
+/// Renders a single rule back into its hashcat/JtR textual rule-language
+/// token, the inverse of [`parse_rule`]/[`grammar::rules::op`].
+///
+/// [`Rule::End`] has no opcode that produces it and renders as an empty
+/// string; likewise [`RejectRule::RequiresClass`] and
+/// [`RejectRule::AllowedOnly`] are password-policy-only (see [`crate::policy`])
+/// and have no textual rule-language form.
+pub fn to_rule_string(rule: &Rule) -> String {
+    match rule {
+        Rule::NoOp => ":".to_string(),
+        Rule::End => String::new(),
+        Rule::Transform(transform) => transform_to_string(transform),
+        Rule::Reject(reject) => reject_to_string(reject),
+    }
+}
+
+fn transform_to_string(rule: &TransformRule) -> String {
+    match rule {
+        TransformRule::Lowercase => "l".to_string(),
+        TransformRule::Uppercase => "u".to_string(),
+        TransformRule::Capitalize => "c".to_string(),
+        TransformRule::InvertCapitalize => "C".to_string(),
+        TransformRule::ToggleCase(None) => "t".to_string(),
+        TransformRule::ToggleCase(Some(n)) => format!("T{n}"),
+        TransformRule::Reverse => "r".to_string(),
+        TransformRule::Duplicate(None) => "d".to_string(),
+        TransformRule::Duplicate(Some(n)) => format!("p{n}"),
+        TransformRule::Reflect => "f".to_string(),
+        TransformRule::Rotate(Rotation::Left) => "{".to_string(),
+        TransformRule::Rotate(Rotation::Right) => "}".to_string(),
+        // `simplify` coalesces runs of `$`/`^` into one multi-char
+        // Append/Prepend, so emit one token per character to stay valid.
+        TransformRule::Append(s) => s.chars().map(|c| format!("${c}")).collect(),
+        TransformRule::Prepend(s) => s.chars().rev().map(|c| format!("^{c}")).collect(),
+        TransformRule::Delete(n) => format!("D{n}"),
+        TransformRule::Extract(a, b) => format!("x{a}:{b}"),
+        TransformRule::Omit(a, b) => format!("O{a}:{b}"),
+        TransformRule::Insert(n, s) => format!("i{s}{n}"),
+        TransformRule::Overwrite(n, s) => format!("o{n}{s}"),
+        TransformRule::Truncate(Truncate::Left) => "[".to_string(),
+        TransformRule::Truncate(Truncate::Right) => "]".to_string(),
+        TransformRule::Truncate(Truncate::To(n)) => format!("'{n}"),
+        TransformRule::Replace(a, b) => format!("s{a}{b}"),
+        TransformRule::Purge(s) => format!("@{s}"),
+        TransformRule::DuplicateFirst(n) => format!("z{n}"),
+        TransformRule::DuplicateLast(n) => format!("Z{n}"),
+        TransformRule::DuplicateAll => "q".to_string(),
+        TransformRule::SwapFront => "k".to_string(),
+        TransformRule::SwapBack => "K".to_string(),
+        TransformRule::Swap(a, b) => format!("*{a}:{b}"),
+        TransformRule::BitwiseShiftLeft(n) => format!("L{n}"),
+        TransformRule::BitwiseShiftRight(n) => format!("R{n}"),
+        TransformRule::AsciiIncrement(n) => format!("+{n}"),
+        TransformRule::AsciiDecrement(n) => format!("-{n}"),
+        TransformRule::ReplaceWithNext(n) => format!(".{n}"),
+        TransformRule::ReplaceWithPrev(n) => format!(",{n}"),
+        TransformRule::DuplicateFirstBlock(n) => format!("y{n}"),
+        TransformRule::DuplicateLastBlock(n) => format!("Y{n}"),
+        TransformRule::TitleCase => "~wT".to_string(),
+        TransformRule::AlternatingWordCase => "~wA".to_string(),
+        TransformRule::RegexReplace(pattern, replacement) => {
+            format!("~s/{}/{}/", escape_delimiter(pattern), escape_delimiter(replacement))
+        }
+        TransformRule::ToCase(Case::Title) => "~cT".to_string(),
+        TransformRule::ToCase(Case::Camel) => "~cC".to_string(),
+        TransformRule::ToCase(Case::Pascal) => "~cP".to_string(),
+        TransformRule::ToCase(Case::Snake) => "~cS".to_string(),
+        TransformRule::ToCase(Case::Kebab) => "~cK".to_string(),
+        TransformRule::ToCase(Case::ScreamingSnake) => "~cU".to_string(),
+        TransformRule::CasePermute(limit) => format!("~p{limit}"),
+        TransformRule::LeetReplace(table) => {
+            let subs: String = table
+                .iter()
+                .map(|(from, to)| format!("{from}{}{}", to.len(), to.iter().collect::<String>()))
+                .collect();
+            format!("~l{subs}")
+        }
+    }
+}
+
+fn reject_to_string(rule: &RejectRule) -> String {
+    match rule {
+        RejectRule::LongerThan(n) => format!("<{n}"),
+        RejectRule::ShorterThan(n) => format!(">{n}"),
+        RejectRule::NotEqualTo(n) => format!("_{n}"),
+        RejectRule::Contains(s) => format!("!{s}"),
+        RejectRule::NotContains(s) => format!("/{s}"),
+        RejectRule::NotStartsWith(s) => format!("({s}"),
+        RejectRule::NotEndsWith(s) => format!("){s}"),
+        RejectRule::NotEqualAt(n, s) => format!("={n}{s}"),
+        RejectRule::ContainsLessThan(n, s) => format!("%{n}{s}"),
+        RejectRule::RegexMatch(pattern) => format!("~m/{}/", escape_delimiter(pattern)),
+        RejectRule::RegexNotMatch(pattern) => format!("~M/{}/", escape_delimiter(pattern)),
+        RejectRule::RequiresClass(_) | RejectRule::AllowedOnly(_) => String::new(),
+    }
+}
+
+/// Escapes a literal `/` (the delimiter [`transform_to_string`]/[`reject_to_string`]
+/// always pick for regex rules) so the pattern/replacement round-trips through
+/// [`delimited_field`].
+fn escape_delimiter(s: &str) -> String {
+    s.replace('/', "\\/")
 }
\ No newline at end of file
@@ -0,0 +1,113 @@
+//! Compiler for password-policy specifications into reject rules.
+//!
+//! A specification is a `;`-separated list of `key: value` statements, where
+//! `value` may itself be a comma-separated list, e.g.:
+//!
+//! ```text
+//! minlength: 8; maxlength: 64; required: lower, upper, digit; required: special; allowed: ascii-printable
+//! ```
+//!
+//! Compiling a spec produces a `Vec<Rule>` of [`RejectRule`]s that, applied
+//! through the engine, filter a wordlist down to policy-compliant candidates.
+
+use crate::lang::{CharClass, RejectRule, Rule};
+
+/// An error produced while compiling a password-policy specification.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PolicyError {
+    /// A `key: value` statement was missing the `:` separator.
+    MalformedStatement(String),
+    /// The key on the left of `:` is not a recognized policy directive.
+    UnknownKey(String),
+    /// A character-class name in a `required`/`allowed` list wasn't recognized.
+    UnknownClass(String),
+    /// `minlength`/`maxlength` value wasn't a valid non-negative integer.
+    InvalidNumber(String),
+}
+
+impl std::fmt::Display for PolicyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PolicyError::MalformedStatement(s) => write!(f, "malformed policy statement: `{}`", s),
+            PolicyError::UnknownKey(k) => write!(f, "unknown policy key: `{}`", k),
+            PolicyError::UnknownClass(c) => write!(f, "unknown character class: `{}`", c),
+            PolicyError::InvalidNumber(n) => write!(f, "invalid number: `{}`", n),
+        }
+    }
+}
+
+impl std::error::Error for PolicyError {}
+
+fn parse_class(name: &str) -> Result<CharClass, PolicyError> {
+    match name.trim().to_lowercase().as_str() {
+        "lower" => Ok(CharClass::Lower),
+        "upper" => Ok(CharClass::Upper),
+        "digit" => Ok(CharClass::Digit),
+        "special" => Ok(CharClass::Special),
+        "unicode" => Ok(CharClass::Unicode),
+        other => Err(PolicyError::UnknownClass(other.to_string())),
+    }
+}
+
+/// Expands a comma-separated `allowed`/`required` value into character classes,
+/// treating the `ascii-printable` shorthand as lower+upper+digit+special.
+fn parse_class_list(value: &str) -> Result<Vec<CharClass>, PolicyError> {
+    let mut classes = Vec::new();
+    for token in value.split(',') {
+        let token = token.trim();
+        if token.eq_ignore_ascii_case("ascii-printable") {
+            classes.extend([CharClass::Lower, CharClass::Upper, CharClass::Digit, CharClass::Special]);
+        } else {
+            classes.push(parse_class(token)?);
+        }
+    }
+    Ok(classes)
+}
+
+/// Compiles a password-policy specification into a sequence of reject rules.
+///
+/// # Examples
+///
+/// ```
+/// # use crate::_core::policy::compile_policy;
+/// # use crate::_core::lang::{Rule, RejectRule, CharClass};
+/// let rules = compile_policy("minlength: 8; required: digit").unwrap();
+/// assert_eq!(rules, vec![
+///     Rule::Reject(RejectRule::ShorterThan(8)),
+///     Rule::Reject(RejectRule::RequiresClass(CharClass::Digit)),
+/// ]);
+/// ```
+pub fn compile_policy(spec: &str) -> Result<Vec<Rule>, PolicyError> {
+    let mut rules = Vec::new();
+    for statement in spec.split(';') {
+        let statement = statement.trim();
+        if statement.is_empty() {
+            continue;
+        }
+        let (key, value) = statement
+            .split_once(':')
+            .ok_or_else(|| PolicyError::MalformedStatement(statement.to_string()))?;
+        let key = key.trim().to_lowercase();
+        let value = value.trim();
+        match key.as_str() {
+            "minlength" => {
+                let n: usize = value.parse().map_err(|_| PolicyError::InvalidNumber(value.to_string()))?;
+                rules.push(Rule::Reject(RejectRule::ShorterThan(n)));
+            }
+            "maxlength" => {
+                let n: usize = value.parse().map_err(|_| PolicyError::InvalidNumber(value.to_string()))?;
+                rules.push(Rule::Reject(RejectRule::LongerThan(n)));
+            }
+            "required" => {
+                for class in parse_class_list(value)? {
+                    rules.push(Rule::Reject(RejectRule::RequiresClass(class)));
+                }
+            }
+            "allowed" => {
+                rules.push(Rule::Reject(RejectRule::AllowedOnly(parse_class_list(value)?)));
+            }
+            other => return Err(PolicyError::UnknownKey(other.to_string())),
+        }
+    }
+    Ok(rules)
+}
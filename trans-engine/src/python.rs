@@ -1,19 +1,90 @@
+//! PyO3 entry points exposed to Python as the `_core` extension module.
+//!
+//! Each function here is a thin wrapper around the corresponding `engine`,
+//! `codec` or `generator` entry point, translating a `Result<_, String>`
+//! into a `PyResult` so errors surface as Python exceptions.
 
 use pyo3::prelude::*;
-pub mod lang;
-pub mod parser;
-pub mod engine;
+use crate::codec;
+use crate::engine;
+use crate::lang::RuleMode;
+use crate::parser::ParseMode;
+use crate::generator::{self, CasePolicy, GenConfig, InsertionPolicy};
 
 #[pyfunction]
-pub fn run(rules: Vec<String>, words: Vec<String>) -> PyResult<Vec<String>> {
-    engine::run(rules, words).map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e))
+#[pyo3(signature = (rules, words, verbose=false, unicode=false))]
+pub fn run(rules: Vec<String>, words: Vec<String>, verbose: bool, unicode: bool) -> PyResult<Vec<String>> {
+    let mode = if verbose { ParseMode::Verbose } else { ParseMode::Strict };
+    let rule_mode = if unicode { RuleMode::Unicode } else { RuleMode::Bytes };
+    engine::run_collecting_rule_mode(rules, words, mode, rule_mode)
+        .map(|(output, _errors)| output)
+        .map_err(PyErr::new::<pyo3::exceptions::PyRuntimeError, _>)
 }
 
-/// A Python module implemented in Rust. The name of this function must match
-/// the `lib.name` setting in the `Cargo.toml`, else Python will not be able to
-/// import the module.
-#[pymodule]
-fn _core(m: &Bound<'_, PyModule>) -> PyResult<()> {
-    m.add_function(wrap_pyfunction!(run, m)?)?;
-    Ok(())
+#[pyfunction]
+pub fn filter_by_policy(spec: String, words: Vec<String>) -> PyResult<Vec<String>> {
+    engine::filter_by_policy(&spec, words).map_err(PyErr::new::<pyo3::exceptions::PyRuntimeError, _>)
+}
+
+/// Compiles `rules` into a binary program that can be cached and later run
+/// with [`run_compiled`] instead of re-parsing the rule lines every call.
+#[pyfunction]
+pub fn compile(rules: Vec<String>) -> PyResult<Vec<u8>> {
+    codec::compile(&rules).map_err(PyErr::new::<pyo3::exceptions::PyRuntimeError, _>)
+}
+
+#[pyfunction]
+pub fn run_compiled(program: Vec<u8>, words: Vec<String>) -> PyResult<Vec<String>> {
+    codec::run_compiled(&program, words).map_err(PyErr::new::<pyo3::exceptions::PyRuntimeError, _>)
+}
+
+#[pyfunction]
+#[pyo3(signature = (
+    text,
+    min_word_length=3,
+    candidate_count=100,
+    target_min_length=8,
+    target_max_length=16,
+    num_digits=1,
+    num_specials=1,
+    keep_numbers=true,
+    case_policy="capitalize-first",
+    word_boundary_only=true,
+))]
+#[allow(clippy::too_many_arguments)]
+pub fn generate(
+    text: String,
+    min_word_length: usize,
+    candidate_count: usize,
+    target_min_length: usize,
+    target_max_length: usize,
+    num_digits: usize,
+    num_specials: usize,
+    keep_numbers: bool,
+    case_policy: &str,
+    word_boundary_only: bool,
+) -> PyResult<Vec<String>> {
+    let case_policy = match case_policy {
+        "capitalize-first" => CasePolicy::CapitalizeFirst,
+        "lowercase" => CasePolicy::Lowercase,
+        "uppercase" => CasePolicy::Uppercase,
+        "random-toggle" => CasePolicy::RandomToggle,
+        other => {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "unknown case_policy `{other}`; expected one of: capitalize-first, lowercase, uppercase, random-toggle"
+            )))
+        }
+    };
+    let config = GenConfig {
+        min_word_length,
+        candidate_count,
+        target_min_length,
+        target_max_length,
+        num_digits,
+        num_specials,
+        keep_numbers,
+        case_policy,
+        insertion: if word_boundary_only { InsertionPolicy::WordBoundaryOnly } else { InsertionPolicy::Uniform },
+    };
+    Ok(generator::generate(&text, &config))
 }
@@ -0,0 +1,67 @@
+//! A small dual byte/code-unit buffer, modeled on Ruffle's `WStr`/`Units`
+//! design. [`crate::lang::RuleMode::Bytes`] processes a word as its raw UTF-8
+//! bytes, so byte-oriented rules (`L`/`R` bitwise shift, `+`/`-` ASCII
+//! increment/decrement) touch individual bytes losslessly instead of
+//! truncating a full `char` down to `as u8` and corrupting every byte of a
+//! multibyte sequence. [`crate::lang::RuleMode::Unicode`] instead processes
+//! it as a buffer of whole Unicode scalar values, so those same rules (and
+//! length/index-based reject rules) operate on codepoints rather than bytes.
+//! Conversion back to a `String` happens once, at the boundary, with a
+//! lossy-UTF-8 fallback for any byte sequence a byte-level transform has left
+//! invalid.
+
+use crate::lang::RuleMode;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Units {
+    Bytes(Vec<u8>),
+    Unicode(Vec<char>),
+}
+
+impl Units {
+    pub fn from_str(input: &str, mode: RuleMode) -> Self {
+        match mode {
+            RuleMode::Bytes => Units::Bytes(input.as_bytes().to_vec()),
+            RuleMode::Unicode => Units::Unicode(input.chars().collect()),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        match self {
+            Units::Bytes(b) => b.len(),
+            Units::Unicode(u) => u.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Applies `f` to every unit's numeric value - each raw byte in
+    /// [`Units::Bytes`], or each codepoint's `u32` scalar value in
+    /// [`Units::Unicode`] - and rebuilds the buffer from the results. A
+    /// result that isn't a valid scalar value (possible after shifting or
+    /// wrapping a codepoint) falls back to the Unicode replacement
+    /// character rather than panicking.
+    pub fn map_units(self, f: impl Fn(u32) -> u32) -> Units {
+        match self {
+            Units::Bytes(bytes) => Units::Bytes(bytes.into_iter().map(|b| f(b as u32) as u8).collect()),
+            Units::Unicode(chars) => Units::Unicode(
+                chars
+                    .into_iter()
+                    .map(|c| char::from_u32(f(c as u32)).unwrap_or(char::REPLACEMENT_CHARACTER))
+                    .collect(),
+            ),
+        }
+    }
+
+    /// Converts back to a `String` at the processing boundary; any bytes
+    /// left invalid by a byte-level transform are replaced per
+    /// [`String::from_utf8_lossy`].
+    pub fn into_string(self) -> String {
+        match self {
+            Units::Bytes(bytes) => String::from_utf8_lossy(&bytes).into_owned(),
+            Units::Unicode(chars) => chars.into_iter().collect(),
+        }
+    }
+}
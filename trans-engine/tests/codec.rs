@@ -0,0 +1,43 @@
+//! Integration tests for the binary rule-program codec: round-tripping a
+//! compiled program through [`_core::codec::run_compiled`] should match
+//! running the same rule lines straight through [`_core::engine::run`].
+
+use _core::codec::{compile, run_compiled};
+use _core::engine;
+
+#[test]
+fn compile_then_run_compiled_matches_running_the_rule_lines_directly() {
+    let rules = vec!["lu".to_string(), "c$1".to_string()];
+    let words = vec!["Password".to_string(), "admin".to_string()];
+
+    let program = compile(&rules).unwrap();
+    let decoded_result = run_compiled(&program, words.clone()).unwrap();
+    let direct_result = engine::run(rules, words).unwrap();
+
+    assert_eq!(decoded_result, direct_result);
+}
+
+#[test]
+fn compile_then_run_compiled_round_trips_regex_and_reject_rules() {
+    let rules = vec![r"~s/(\d+)$/<$1>/".to_string(), ">4!z".to_string()];
+    let words = vec!["hunter2".to_string(), "zoo".to_string(), "cat".to_string()];
+
+    let program = compile(&rules).unwrap();
+    let decoded_result = run_compiled(&program, words.clone()).unwrap();
+    let direct_result = engine::run(rules, words).unwrap();
+
+    assert_eq!(decoded_result, direct_result);
+}
+
+#[test]
+fn compile_surfaces_parse_errors() {
+    let rules = vec!["luc|".to_string()];
+    assert!(compile(&rules).is_err());
+}
+
+#[test]
+fn run_compiled_rejects_truncated_programs() {
+    let program = compile(&["l".to_string()]).unwrap();
+    let truncated = &program[..program.len() - 1];
+    assert!(run_compiled(truncated, vec!["word".to_string()]).is_err());
+}
@@ -3,7 +3,8 @@
 //! These tests verify that the rule application system correctly transforms
 //! input words according to Hashcat rule specifications.
 
-use _core::engine::run;
+use _core::engine::{run, run_mode};
+use _core::parser::ParseMode;
 
 #[test]
 fn test_basic_transform_rules() {
@@ -276,6 +277,127 @@ fn test_complex_rule_combinations() {
     assert_eq!(results, expected);
 }
 
+#[test]
+fn test_regex_rules() {
+    let rules = vec![
+        r"~s/(\d+)$/<$1>/".to_string(),  // wrap trailing digits in angle brackets
+        "~m/^admin/".to_string(),        // reject if starts with "admin"
+    ];
+
+    let words = vec![
+        "password123".to_string(),
+        "admin99".to_string(),
+    ];
+
+    let results = run(rules, words).unwrap();
+
+    let expected = vec![
+        "password<123>".to_string(),  // ~s -> password123 -> password<123>
+        "admin<99>".to_string(),      // ~s -> admin99 -> admin<99>
+
+        "password123".to_string(),    // ~m -> password123 passes (doesn't start with admin)
+    ];
+
+    assert_eq!(results, expected);
+}
+
+#[test]
+fn test_word_boundary_case_conversion() {
+    let rules = vec![
+        "~cP".to_string(),  // PascalCase
+        "~cC".to_string(),  // camelCase
+        "~cS".to_string(),  // snake_case
+    ];
+
+    let words = vec!["password_reset".to_string(), "HTTPServer".to_string()];
+
+    let results = run(rules, words).unwrap();
+
+    let expected = vec![
+        "PasswordReset".to_string(),  // ~cP -> password_reset -> PasswordReset
+        "HttpServer".to_string(),     // ~cP -> HTTPServer -> HttpServer (HTTP | Server segments)
+
+        "passwordReset".to_string(),  // ~cC -> password_reset -> passwordReset
+        "httpServer".to_string(),     // ~cC -> HTTPServer -> httpServer
+
+        "password_reset".to_string(), // ~cS -> password_reset -> password_reset (already snake_case)
+        "http_server".to_string(),    // ~cS -> HTTPServer -> http_server
+    ];
+
+    assert_eq!(results, expected);
+}
+
+#[test]
+fn test_verbose_mode_run() {
+    let rules = vec!["l  $1  # lowercase then append one".to_string()];
+    let words = vec!["PASSWORD".to_string()];
+
+    let results = run_mode(rules, words, ParseMode::Verbose).unwrap();
+
+    assert_eq!(results, vec!["password1".to_string()]);
+}
+
+#[test]
+fn test_filter_by_policy() {
+    use _core::engine::filter_by_policy;
+
+    let words = vec![
+        "short".to_string(),           // too short, missing upper/digit
+        "password123".to_string(),     // missing upper
+        "Password123".to_string(),     // satisfies the policy
+    ];
+
+    let results = filter_by_policy("minlength: 8; required: lower, upper, digit", words).unwrap();
+
+    assert_eq!(results, vec!["Password123".to_string()]);
+}
+
+#[test]
+fn test_run_collecting_surfaces_structured_parse_errors() {
+    use _core::engine::run_collecting;
+
+    let rules = vec!["l".to_string(), "luc|".to_string()];
+    let words = vec!["Password".to_string()];
+
+    let (output, errors) = run_collecting(rules, words, ParseMode::Strict).unwrap();
+
+    assert_eq!(output, vec!["password".to_string()]);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].found, Some('|'));
+}
+
+#[test]
+fn test_run_streaming_applies_every_rule_line_to_every_word() {
+    use _core::engine::run_streaming;
+
+    let rules = vec!["l".to_string(), "u".to_string()];
+    let words = vec!["Password".to_string(), "Admin".to_string()].into_iter();
+
+    let mut results: Vec<String> = run_streaming(rules, words, ParseMode::Strict).unwrap().collect();
+    results.sort();
+
+    assert_eq!(results, vec![
+        "ADMIN".to_string(),
+        "PASSWORD".to_string(),
+        "admin".to_string(),
+        "password".to_string(),
+    ]);
+}
+
+#[test]
+fn test_run_streaming_reader_reads_words_from_bufread() {
+    use std::io::Cursor;
+    use _core::engine::run_streaming_reader;
+
+    let rules = vec!["c".to_string()];
+    let reader = Cursor::new("password\nadmin\n");
+
+    let mut results: Vec<String> = run_streaming_reader(rules, reader, ParseMode::Strict).unwrap().collect();
+    results.sort();
+
+    assert_eq!(results, vec!["Admin".to_string(), "Password".to_string()]);
+}
+
 #[test]
 fn test_error_handling() {
     let invalid_rules = vec![
@@ -283,13 +405,70 @@ fn test_error_handling() {
         "x".to_string(),         // missing range parameter
         "T".to_string(),         // missing position parameter
     ];
-    
+
     let words = vec!["password".to_string()];
-    
+
     // The function should still return a result, but it might be empty
     // or contain error messages depending on implementation
     let results = run(invalid_rules, words).unwrap();
-    
+
     // Expecting empty results since all rules are invalid
     assert!(results.is_empty());
+}
+
+#[test]
+fn test_run_combining_union_concatenates_every_ruleset_independently() {
+    use _core::engine::{run_combining, Combine};
+
+    let rulesets = vec![
+        vec!["l".to_string()],
+        vec!["u".to_string()],
+    ];
+    let words = vec!["Password".to_string()];
+
+    let mut results = run_combining(rulesets, words, ParseMode::Strict, Combine::Union, false).unwrap();
+    results.sort();
+
+    assert_eq!(results, vec!["PASSWORD".to_string(), "password".to_string()]);
+}
+
+#[test]
+fn test_run_combining_chain_feeds_each_rulesets_output_into_the_next() {
+    use _core::engine::{run_combining, Combine};
+
+    let rulesets = vec![
+        vec!["l".to_string()],
+        vec!["$1".to_string()],
+    ];
+    let words = vec!["Password".to_string()];
+
+    let results = run_combining(rulesets, words, ParseMode::Strict, Combine::Chain, false).unwrap();
+
+    assert_eq!(results, vec!["password1".to_string()]);
+}
+
+#[test]
+fn test_run_combining_dedup_collapses_duplicate_candidates_between_stages() {
+    use _core::engine::{run_combining, Combine};
+
+    let rulesets = vec![
+        vec!["l".to_string(), "l".to_string()],
+        vec![":".to_string()],
+    ];
+    let words = vec!["Password".to_string()];
+
+    let results = run_combining(rulesets, words, ParseMode::Strict, Combine::Chain, true).unwrap();
+
+    assert_eq!(results, vec!["password".to_string()]);
+}
+
+#[test]
+fn test_run_fans_out_a_case_permute_rule_line_into_every_variant() {
+    let rules = vec!["~p2".to_string()];
+    let words = vec!["ab".to_string()];
+
+    let mut results = run(rules, words).unwrap();
+    results.sort();
+
+    assert_eq!(results, vec!["AB".to_string(), "Ab".to_string(), "aB".to_string(), "ab".to_string()]);
 }
\ No newline at end of file
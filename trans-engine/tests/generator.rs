@@ -0,0 +1,66 @@
+//! Integration tests for the passphrase generator.
+
+use _core::generator::{generate, CasePolicy, GenConfig, InsertionPolicy};
+
+#[test]
+fn generate_produces_requested_count_or_fewer() {
+    let config = GenConfig { candidate_count: 10, ..GenConfig::default() };
+    let candidates = generate("the quick brown fox jumps over the lazy dog", &config);
+    assert!(!candidates.is_empty());
+    assert!(candidates.len() <= 10);
+}
+
+#[test]
+fn generate_candidates_are_distinct() {
+    let config = GenConfig { candidate_count: 20, ..GenConfig::default() };
+    let candidates = generate("lorem ipsum dolor sit amet consectetur adipiscing elit", &config);
+    let distinct: std::collections::HashSet<_> = candidates.iter().collect();
+    assert_eq!(distinct.len(), candidates.len());
+}
+
+#[test]
+fn generate_respects_length_bounds_before_padding() {
+    let config = GenConfig {
+        candidate_count: 20,
+        target_min_length: 6,
+        target_max_length: 10,
+        num_digits: 0,
+        num_specials: 0,
+        ..GenConfig::default()
+    };
+    let candidates = generate("alpha beta gamma delta epsilon", &config);
+    for candidate in candidates {
+        assert!(candidate.chars().count() <= config.target_max_length + 2);
+    }
+}
+
+#[test]
+fn generate_discards_short_tokens_but_keeps_numbers_when_configured() {
+    let config = GenConfig { min_word_length: 5, candidate_count: 5, keep_numbers: true, ..GenConfig::default() };
+    // "at", "42" are shorter than min_word_length; "at" should be discarded, "42" retained.
+    let candidates = generate("at 42 password reset", &config);
+    assert!(!candidates.is_empty());
+}
+
+#[test]
+fn generate_empty_input_yields_no_candidates() {
+    let config = GenConfig::default();
+    let candidates = generate("## !! ??", &config);
+    assert!(candidates.is_empty());
+}
+
+#[test]
+fn generate_with_uniform_insertion_still_contains_padding() {
+    let config = GenConfig {
+        candidate_count: 5,
+        num_digits: 2,
+        num_specials: 1,
+        insertion: InsertionPolicy::Uniform,
+        case_policy: CasePolicy::Lowercase,
+        ..GenConfig::default()
+    };
+    let candidates = generate("password reset example word list", &config);
+    for candidate in candidates {
+        assert!(candidate.chars().any(|c| c.is_ascii_digit()));
+    }
+}
@@ -0,0 +1,148 @@
+//! Integration tests for `lang::Rule::run_all`'s borrow-preserving pipeline.
+
+use std::borrow::Cow;
+
+use _core::lang::{Rule, RejectRule, RuleMode, TransformRule};
+
+#[test]
+fn run_all_borrows_through_reject_only_chain() {
+    let rules = vec![
+        Rule::Reject(RejectRule::ShorterThan(3)),
+        Rule::Reject(RejectRule::LongerThan(16)),
+    ];
+    let word = "password";
+
+    let result = Rule::run_all(&rules, word).unwrap();
+
+    assert_eq!(result, "password");
+    assert!(matches!(result, Cow::Borrowed(_)));
+}
+
+#[test]
+fn run_all_returns_none_on_rejected_word() {
+    let rules = vec![Rule::Reject(RejectRule::ShorterThan(8))];
+    let word = "short";
+
+    assert_eq!(Rule::run_all(&rules, word), None);
+}
+
+#[test]
+fn run_all_allocates_only_once_a_transform_fires() {
+    let rules = vec![
+        Rule::Reject(RejectRule::ShorterThan(3)),
+        Rule::Transform(TransformRule::Uppercase),
+    ];
+    let word = "password";
+
+    let result = Rule::run_all(&rules, word).unwrap();
+
+    assert_eq!(result, "PASSWORD");
+    assert!(matches!(result, Cow::Owned(_)));
+}
+
+#[test]
+fn run_all_borrows_when_a_transform_rule_does_not_change_the_word() {
+    // `T5` toggles the case of the character at index 5, which doesn't exist
+    // in a 2-char word - the transform runs but produces identical output.
+    let rules = vec![Rule::Transform(TransformRule::ToggleCase(Some(5)))];
+    let word = "ab";
+
+    let result = Rule::run_all(&rules, word).unwrap();
+
+    assert_eq!(result, "ab");
+    assert!(matches!(result, Cow::Borrowed(_)));
+}
+
+#[test]
+fn run_all_noop_and_end_never_allocate() {
+    let rules = vec![Rule::NoOp, Rule::End];
+    let word = "password";
+
+    let result = Rule::run_all(&rules, word).unwrap();
+
+    assert_eq!(result, "password");
+    assert!(matches!(result, Cow::Borrowed(_)));
+}
+
+#[test]
+fn bitwise_shift_in_bytes_mode_mangles_every_byte_of_a_multibyte_char() {
+    let rule = Rule::Transform(TransformRule::BitwiseShiftLeft(1));
+
+    // 'é' is the two-byte UTF-8 sequence [0xC3, 0xA9]; shifting each byte
+    // independently does not round-trip back to valid UTF-8.
+    let rules = [rule];
+    let result = Rule::run_all_mode(&rules, "é", RuleMode::Bytes).unwrap();
+
+    assert_eq!(result, String::from_utf8_lossy(&[0xC3u8 << 1, 0xA9u8 << 1]).into_owned());
+}
+
+#[test]
+fn bitwise_shift_in_unicode_mode_operates_on_the_whole_codepoint() {
+    let rule = Rule::Transform(TransformRule::BitwiseShiftLeft(1));
+
+    let rules = [rule];
+    let result = Rule::run_all_mode(&rules, "é", RuleMode::Unicode).unwrap();
+
+    assert_eq!(result, char::from_u32(('é' as u32) << 1).unwrap().to_string());
+}
+
+#[test]
+fn length_reject_rules_count_bytes_in_bytes_mode_and_codepoints_in_unicode_mode() {
+    let rules = vec![Rule::Reject(RejectRule::NotEqualTo(1))];
+    let word = "é"; // 1 codepoint, 2 UTF-8 bytes
+
+    assert_eq!(Rule::run_all_mode(&rules, word, RuleMode::Bytes), None);
+    assert_eq!(Rule::run_all_mode(&rules, word, RuleMode::Unicode).unwrap(), "é");
+}
+
+#[test]
+fn run_keeps_the_first_variant_of_case_permute_and_leet_replace() {
+    let permute = Rule::Transform(TransformRule::CasePermute(2));
+    assert_eq!(permute.run("ab".to_string()), Some("ab".to_string()));
+
+    let leet = Rule::Transform(TransformRule::LeetReplace(vec![('a', vec!['@', '4'])]));
+    assert_eq!(leet.run("password".to_string()), Some("password".to_string()));
+}
+
+#[test]
+fn case_permute_run_many_emits_every_combination_up_to_the_limit() {
+    let rule = Rule::Transform(TransformRule::CasePermute(2));
+
+    let mut results = rule.run_many("ab".to_string());
+    results.sort();
+
+    assert_eq!(results, vec!["AB", "Ab", "aB", "ab"]);
+}
+
+#[test]
+fn leet_replace_run_many_emits_the_cartesian_product_of_substitutions() {
+    let rule = Rule::Transform(TransformRule::LeetReplace(vec![('a', vec!['@', '4'])]));
+
+    let mut results = rule.run_many("aa".to_string());
+    results.sort();
+
+    assert_eq!(results, vec!["44", "4@", "4a", "@4", "@@", "@a", "a4", "a@", "aa"]);
+}
+
+#[test]
+fn run_all_many_chains_expanding_rules_as_a_cartesian_product() {
+    let rules = vec![
+        Rule::Transform(TransformRule::LeetReplace(vec![('a', vec!['@'])])),
+        Rule::Transform(TransformRule::CasePermute(1)),
+    ];
+
+    let mut results = Rule::run_all_many(&rules, "a");
+    results.sort();
+
+    assert_eq!(results, vec!["@", "A", "a"]);
+}
+
+#[test]
+fn run_all_many_stops_early_once_a_reject_rule_empties_every_candidate() {
+    let rules = vec![
+        Rule::Transform(TransformRule::CasePermute(1)),
+        Rule::Reject(RejectRule::ShorterThan(5)),
+    ];
+
+    assert_eq!(Rule::run_all_many(&rules, "a"), Vec::<String>::new());
+}
@@ -5,8 +5,8 @@
 //! independent of each other.
 //!
 
-use _core::lang::{Rule, TransformRule, RejectRule, Rotation, Truncate};
-use _core::parser::parse_line;
+use _core::lang::{Rule, TransformRule, RejectRule, Rotation, Truncate, Case};
+use _core::parser::{parse_line, parse_line_mode, register_operator_extension, rules_to_string, ParseMode};
 
 #[test]
 fn parse_basic_transform_rules() {
@@ -214,6 +214,75 @@ fn parse_nonexistent_rules() {
     }
 }
 
+#[test]
+fn parse_regex_substitution_rule() {
+    let mut input = r"~s/(\d+)$/<$1>/";
+    let result = parse_line(&mut input).unwrap();
+    assert_eq!(result, vec![
+        Rule::Transform(TransformRule::RegexReplace(r"(\d+)$".to_string(), "<$1>".to_string())),
+    ]);
+}
+
+#[test]
+fn parse_regex_reject_rules() {
+    let mut input = "~m/^admin/~M/[0-9]/";
+    let result = parse_line(&mut input).unwrap();
+    assert_eq!(result, vec![
+        Rule::Reject(RejectRule::RegexMatch("^admin".to_string())),
+        Rule::Reject(RejectRule::RegexNotMatch("[0-9]".to_string())),
+    ]);
+}
+
+#[test]
+fn parse_regex_rule_with_escaped_delimiter() {
+    let mut input = r"~s/a\/b/c/";
+    let result = parse_line(&mut input).unwrap();
+    assert_eq!(result, vec![
+        Rule::Transform(TransformRule::RegexReplace("a/b".to_string(), "c".to_string())),
+    ]);
+}
+
+#[test]
+fn parse_case_conversion_rules() {
+    let mut input = "~cT~cC~cP~cS~cK~cU";
+    let result = parse_line(&mut input).unwrap();
+    assert_eq!(result, vec![
+        Rule::Transform(TransformRule::ToCase(Case::Title)),
+        Rule::Transform(TransformRule::ToCase(Case::Camel)),
+        Rule::Transform(TransformRule::ToCase(Case::Pascal)),
+        Rule::Transform(TransformRule::ToCase(Case::Snake)),
+        Rule::Transform(TransformRule::ToCase(Case::Kebab)),
+        Rule::Transform(TransformRule::ToCase(Case::ScreamingSnake)),
+    ]);
+}
+
+#[test]
+fn parse_word_case_rules() {
+    let mut input = "~wT~wA";
+    let result = parse_line(&mut input).unwrap();
+    assert_eq!(result, vec![
+        Rule::Transform(TransformRule::TitleCase),
+        Rule::Transform(TransformRule::AlternatingWordCase),
+    ]);
+}
+
+#[test]
+fn parse_verbose_mode_skips_whitespace_and_comments() {
+    let mut input = "l  $1  # lowercase then append one";
+    let result = parse_line_mode(&mut input, ParseMode::Verbose).unwrap();
+    assert_eq!(result, vec![
+        Rule::Transform(TransformRule::Lowercase),
+        Rule::Transform(TransformRule::Append("1".to_string())),
+        Rule::NoOp,
+    ]);
+}
+
+#[test]
+fn parse_strict_mode_still_rejects_whitespace() {
+    let mut input = "l $1";
+    assert!(parse_line_mode(&mut input, ParseMode::Strict).is_err());
+}
+
 #[test]
 fn parse_non_ascii_rules() {
     let mut input = "l√ºc";
@@ -224,4 +293,131 @@ fn parse_non_ascii_rules() {
     if let Err(ref e) = result {
         assert!(e.to_string().contains("Parsing Error"));
     }
+}
+
+#[test]
+fn parse_error_reports_byte_offset_and_caret() {
+    let mut input = "luc|";
+    let err = parse_line(&mut input).unwrap_err();
+
+    assert_eq!(err.byte_offset, 3);
+    assert_eq!(err.line, 1);
+    assert_eq!(err.col, 4);
+    assert_eq!(err.found, Some('|'));
+
+    let rendered = err.to_string();
+    assert!(rendered.contains("luc|"));
+    assert!(rendered.contains("   ^"));
+    assert!(rendered.contains("unexpected character `|`"));
+}
+
+/// A toy extension recognizing `~x`, a made-up "leetspeak-lite" opcode that
+/// isn't part of the built-in grammar.
+fn leet_extension(input: &str) -> Option<(Rule, usize)> {
+    input
+        .strip_prefix("~x")
+        .map(|_| (Rule::Transform(TransformRule::Replace("e".to_string(), "3".to_string())), 2))
+}
+
+#[test]
+fn parse_line_recognizes_registered_operator_extensions() {
+    register_operator_extension(leet_extension);
+
+    let mut input = "l~xu";
+    let result = parse_line(&mut input).unwrap();
+    assert_eq!(result, vec![
+        Rule::Transform(TransformRule::Lowercase),
+        Rule::Transform(TransformRule::Replace("e".to_string(), "3".to_string())),
+        Rule::Transform(TransformRule::Uppercase),
+    ]);
+}
+
+#[test]
+fn rules_to_string_round_trips_a_complex_rule_line() {
+    let mut input = "luct$1^A[D2x1:3O45sa!~cP~wT<8>3!a=2e";
+    let rules = parse_line(&mut input).unwrap();
+
+    let rendered = rules_to_string(&rules);
+    let mut reparsed_input = rendered.as_str();
+    let reparsed = parse_line(&mut reparsed_input).unwrap();
+
+    assert_eq!(reparsed, rules);
+}
+
+#[test]
+fn rules_to_string_splits_coalesced_appends_and_prepends_into_single_char_tokens() {
+    let mut input = "$1$2$3^A^B^C";
+    let rules = Rule::simplify(parse_line(&mut input).unwrap());
+
+    // `simplify` coalesces the runs above into a single multi-char Append and
+    // Prepend, so the rendered text must still be valid, re-parseable syntax.
+    assert_eq!(rules, vec![
+        Rule::Transform(TransformRule::Append("123".to_string())),
+        Rule::Transform(TransformRule::Prepend("CBA".to_string())),
+    ]);
+
+    let rendered = rules_to_string(&rules);
+    assert_eq!(rendered, "$1$2$3^A^B^C");
+
+    let mut reparsed_input = rendered.as_str();
+    let reparsed = Rule::simplify(parse_line(&mut reparsed_input).unwrap());
+    assert_eq!(reparsed, rules);
+}
+
+#[test]
+fn rules_to_string_round_trips_case_permute_and_leet_replace() {
+    let rules = vec![
+        Rule::Transform(TransformRule::CasePermute(2)),
+        Rule::Transform(TransformRule::LeetReplace(vec![('a', vec!['@', '4']), ('e', vec!['3'])])),
+    ];
+
+    let rendered = rules_to_string(&rules);
+    assert_eq!(rendered, "~p2~la2@4e13");
+
+    let mut reparsed_input = rendered.as_str();
+    let reparsed = parse_line(&mut reparsed_input).unwrap();
+    assert_eq!(reparsed, rules);
+}
+
+#[test]
+fn simplify_folds_adjacent_length_rejects_into_their_minimal_form() {
+    let mut input = "<16>3_8";
+    let rules = Rule::simplify(parse_line(&mut input).unwrap());
+
+    // LongerThan(16), ShorterThan(3), NotEqualTo(8) intersect to exactly [8, 8].
+    assert_eq!(rules, vec![Rule::Reject(RejectRule::NotEqualTo(8))]);
+}
+
+#[test]
+fn simplify_collapses_an_unsatisfiable_length_chain_to_a_single_reject_all_rule() {
+    let mut input = ">8<4";
+    let rules = Rule::simplify(parse_line(&mut input).unwrap());
+
+    // ShorterThan(8) then LongerThan(4) can never both pass.
+    assert_eq!(rules, vec![Rule::Reject(RejectRule::ShorterThan(usize::MAX))]);
+}
+
+#[test]
+fn simplify_resets_the_length_interval_across_a_length_changing_transform() {
+    let mut input = ">8$1<4";
+    let rules = Rule::simplify(parse_line(&mut input).unwrap());
+
+    // ShorterThan(8) applies to the word before appending "1"; LongerThan(4)
+    // applies to the word after, so the two intervals must not be merged.
+    assert_eq!(rules, vec![
+        Rule::Reject(RejectRule::ShorterThan(8)),
+        Rule::Transform(TransformRule::Append("1".to_string())),
+        Rule::Reject(RejectRule::LongerThan(4)),
+    ]);
+}
+
+#[test]
+fn parse_error_after_consuming_prior_line_is_relative_to_remaining_input() {
+    let mut input = "lu\nc|x";
+    let _ = parse_line(&mut input).unwrap();
+    let err = parse_line(&mut input).unwrap_err();
+
+    assert_eq!(err.line, 1);
+    assert_eq!(err.col, 2);
+    assert_eq!(err.found, Some('|'));
 }
\ No newline at end of file
@@ -0,0 +1,66 @@
+//! Integration tests for the password-policy specification compiler.
+
+use _core::lang::{CharClass, RejectRule, Rule};
+use _core::policy::{compile_policy, PolicyError};
+
+#[test]
+fn compile_length_bounds() {
+    let rules = compile_policy("minlength: 8; maxlength: 64").unwrap();
+    assert_eq!(rules, vec![
+        Rule::Reject(RejectRule::ShorterThan(8)),
+        Rule::Reject(RejectRule::LongerThan(64)),
+    ]);
+}
+
+#[test]
+fn compile_required_classes_expand_one_rule_per_class() {
+    let rules = compile_policy("required: lower, upper, digit").unwrap();
+    assert_eq!(rules, vec![
+        Rule::Reject(RejectRule::RequiresClass(CharClass::Lower)),
+        Rule::Reject(RejectRule::RequiresClass(CharClass::Upper)),
+        Rule::Reject(RejectRule::RequiresClass(CharClass::Digit)),
+    ]);
+}
+
+#[test]
+fn compile_allowed_ascii_printable_shorthand() {
+    let rules = compile_policy("allowed: ascii-printable").unwrap();
+    assert_eq!(rules, vec![
+        Rule::Reject(RejectRule::AllowedOnly(vec![
+            CharClass::Lower, CharClass::Upper, CharClass::Digit, CharClass::Special,
+        ])),
+    ]);
+}
+
+#[test]
+fn compile_full_spec() {
+    let rules = compile_policy("minlength: 8; maxlength: 64; required: lower, upper, digit; required: special").unwrap();
+    assert_eq!(rules.len(), 6);
+}
+
+#[test]
+fn compile_unknown_key_is_an_error() {
+    let err = compile_policy("minlenght: 8").unwrap_err();
+    assert_eq!(err, PolicyError::UnknownKey("minlenght".to_string()));
+}
+
+#[test]
+fn compile_unknown_class_is_an_error() {
+    let err = compile_policy("required: vowel").unwrap_err();
+    assert_eq!(err, PolicyError::UnknownClass("vowel".to_string()));
+}
+
+#[test]
+fn compile_malformed_statement_is_an_error() {
+    let err = compile_policy("minlength 8").unwrap_err();
+    assert_eq!(err, PolicyError::MalformedStatement("minlength 8".to_string()));
+}
+
+#[test]
+fn char_class_matches_expected_characters() {
+    assert!(CharClass::Lower.matches('a'));
+    assert!(!CharClass::Lower.matches('A'));
+    assert!(CharClass::Digit.matches('5'));
+    assert!(CharClass::Special.matches('@'));
+    assert!(CharClass::Unicode.matches('ü'));
+}